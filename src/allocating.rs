@@ -2,7 +2,7 @@ use alloc::string::String;
 
 use crate::numeric_traits::*;
 use crate::options::FormatSizeOptions;
-use crate::ISizeFormatter;
+use crate::{convert_formatter, FormatSizeError, ISizeFormatter, QuantityFormatter, Unit};
 
 pub fn format_size_i(input: impl ToF64, options: impl AsRef<FormatSizeOptions>) -> String {
     format!("{}", ISizeFormatter::new(input, options))
@@ -12,6 +12,23 @@ pub fn format_size(input: impl ToF64 + Unsigned, options: impl AsRef<FormatSizeO
     format_size_i(input, &options)
 }
 
+/// Formats the size, or reports why it can't be displayed with these options (see `FormatSizeError`).
+/// Values that can't be displayed are formatted as the options' `placeholder` when it is set.
+pub fn try_format_size_i(
+    input: impl ToF64,
+    options: impl AsRef<FormatSizeOptions>,
+) -> Result<String, FormatSizeError> {
+    ISizeFormatter::try_new(input, options).map(|formatter| format!("{}", formatter))
+}
+
+/// The unsigned counterpart of `try_format_size_i`.
+pub fn try_format_size(
+    input: impl ToF64 + Unsigned,
+    options: impl AsRef<FormatSizeOptions>,
+) -> Result<String, FormatSizeError> {
+    try_format_size_i(input, &options)
+}
+
 pub fn make_format_i<T: ToF64>(options: impl AsRef<FormatSizeOptions>) -> impl Fn(T) -> String {
     move |val| -> String { format_size_i(val, &options) }
 }
@@ -21,3 +38,22 @@ pub fn make_format<T: ToF64 + Unsigned>(
 ) -> impl Fn(T) -> String {
     make_format_i(options)
 }
+
+/// Formats an amount of any unit with SI prefixes (see `QuantityFormatter`).
+pub fn format_quantity(
+    input: impl ToF64,
+    unit: &str,
+    options: impl AsRef<FormatSizeOptions>,
+) -> String {
+    format!("{}", QuantityFormatter::new(input, unit, options))
+}
+
+/// Formats an amount of `from` units as an amount of `to` units (see `convert_formatter`).
+pub fn format_converted(
+    input: impl ToF64,
+    from: Unit,
+    to: Unit,
+    options: impl AsRef<FormatSizeOptions>,
+) -> String {
+    format!("{}", convert_formatter(input, from, to, options))
+}
@@ -0,0 +1,379 @@
+//! Describes `ByteSize`, a value type for amounts of bytes which formats and parses itself.
+
+use core::cmp::Ordering;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::Sum;
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use core::str::FromStr;
+
+use crate::{
+    parse_size, FormatSizeOptions, ParseSizeError, ParseSizeOptions, SizeFormatter, ToF64,
+    Unsigned, BINARY,
+};
+
+/// An amount of bytes.
+///
+/// `ByteSize` displays itself through `SizeFormatter` with the `BINARY` options, and parses from any size
+/// `parse_size` understands. `display` and `parse_with` use other options, and `ByteSizeWith` other default ones.
+///
+/// ```rust
+/// use humansize::ByteSize;
+///
+/// let size = ByteSize::mib(3) + ByteSize::kib(512);
+/// assert_eq!(size.to_string(), "3.50 MiB");
+/// assert_eq!("3.5 MiB".parse::<ByteSize>(), Ok(size));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ByteSize(pub u64);
+
+macro_rules! constructors {
+    ($($name:ident = $multiplier:expr, $doc:literal;)*) => ($(
+        #[doc = $doc]
+        pub const fn $name(n: u64) -> ByteSize {
+            ByteSize(n.saturating_mul($multiplier))
+        }
+    )*)
+}
+
+impl ByteSize {
+    pub const ZERO: ByteSize = ByteSize(0);
+    pub const MAX: ByteSize = ByteSize(u64::MAX);
+
+    constructors! {
+        b = 1, "Creates a size of `n` bytes.";
+        kb = 1_000, "Creates a size of `n` kilobytes (1000 bytes), saturating at `ByteSize::MAX`.";
+        mb = 1_000_000, "Creates a size of `n` megabytes, saturating at `ByteSize::MAX`.";
+        gb = 1_000_000_000, "Creates a size of `n` gigabytes, saturating at `ByteSize::MAX`.";
+        tb = 1_000_000_000_000, "Creates a size of `n` terabytes, saturating at `ByteSize::MAX`.";
+        pb = 1_000_000_000_000_000, "Creates a size of `n` petabytes, saturating at `ByteSize::MAX`.";
+        eb = 1_000_000_000_000_000_000, "Creates a size of `n` exabytes, saturating at `ByteSize::MAX`.";
+        kib = 1 << 10, "Creates a size of `n` kibibytes (1024 bytes), saturating at `ByteSize::MAX`.";
+        mib = 1 << 20, "Creates a size of `n` mebibytes, saturating at `ByteSize::MAX`.";
+        gib = 1 << 30, "Creates a size of `n` gibibytes, saturating at `ByteSize::MAX`.";
+        tib = 1 << 40, "Creates a size of `n` tebibytes, saturating at `ByteSize::MAX`.";
+        pib = 1 << 50, "Creates a size of `n` pebibytes, saturating at `ByteSize::MAX`.";
+        eib = 1 << 60, "Creates a size of `n` exbibytes, saturating at `ByteSize::MAX`.";
+    }
+
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    pub const fn checked_add(self, rhs: ByteSize) -> Option<ByteSize> {
+        match self.0.checked_add(rhs.0) {
+            Some(n) => Some(ByteSize(n)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, rhs: ByteSize) -> Option<ByteSize> {
+        match self.0.checked_sub(rhs.0) {
+            Some(n) => Some(ByteSize(n)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_mul(self, rhs: u64) -> Option<ByteSize> {
+        match self.0.checked_mul(rhs) {
+            Some(n) => Some(ByteSize(n)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_div(self, rhs: u64) -> Option<ByteSize> {
+        match self.0.checked_div(rhs) {
+            Some(n) => Some(ByteSize(n)),
+            None => None,
+        }
+    }
+
+    pub const fn saturating_add(self, rhs: ByteSize) -> ByteSize {
+        ByteSize(self.0.saturating_add(rhs.0))
+    }
+
+    pub const fn saturating_sub(self, rhs: ByteSize) -> ByteSize {
+        ByteSize(self.0.saturating_sub(rhs.0))
+    }
+
+    pub const fn saturating_mul(self, rhs: u64) -> ByteSize {
+        ByteSize(self.0.saturating_mul(rhs))
+    }
+
+    /// Returns a formatter displaying this size with the given options instead of `BINARY`.
+    pub fn display<O: AsRef<FormatSizeOptions>>(self, options: O) -> SizeFormatter<u64, O> {
+        SizeFormatter::new(self.0, options)
+    }
+
+    /// Parses a size with the given options instead of `BINARY` (e.g. `WINDOWS` reads `1 kB` as 1024 bytes).
+    pub fn parse_with(
+        s: &str,
+        options: impl Into<ParseSizeOptions>,
+    ) -> Result<ByteSize, ParseSizeError> {
+        parse_size(s, options).map(ByteSize)
+    }
+
+    /// Wraps this size to display, parse and (de)serialize it with the options of `P` by default.
+    pub const fn with_options<P: OptionsPreset>(self) -> ByteSizeWith<P> {
+        ByteSizeWith::new(self)
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.display(BINARY), f)
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ByteSize::parse_with(s, BINARY)
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        ByteSize(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl ToF64 for ByteSize {
+    fn to_f64(&self) -> f64 {
+        self.0 as f64
+    }
+
+    fn to_exact(&self) -> Option<(bool, u128)> {
+        Some((false, self.0 as u128))
+    }
+}
+
+impl Unsigned for ByteSize {}
+
+impl Add for ByteSize {
+    type Output = ByteSize;
+
+    fn add(self, rhs: ByteSize) -> ByteSize {
+        ByteSize(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for ByteSize {
+    fn add_assign(&mut self, rhs: ByteSize) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for ByteSize {
+    type Output = ByteSize;
+
+    fn sub(self, rhs: ByteSize) -> ByteSize {
+        ByteSize(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for ByteSize {
+    fn sub_assign(&mut self, rhs: ByteSize) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Mul<u64> for ByteSize {
+    type Output = ByteSize;
+
+    fn mul(self, rhs: u64) -> ByteSize {
+        ByteSize(self.0 * rhs)
+    }
+}
+
+impl Mul<ByteSize> for u64 {
+    type Output = ByteSize;
+
+    fn mul(self, rhs: ByteSize) -> ByteSize {
+        ByteSize(self * rhs.0)
+    }
+}
+
+impl MulAssign<u64> for ByteSize {
+    fn mul_assign(&mut self, rhs: u64) {
+        self.0 *= rhs;
+    }
+}
+
+impl Div<u64> for ByteSize {
+    type Output = ByteSize;
+
+    fn div(self, rhs: u64) -> ByteSize {
+        ByteSize(self.0 / rhs)
+    }
+}
+
+impl DivAssign<u64> for ByteSize {
+    fn div_assign(&mut self, rhs: u64) {
+        self.0 /= rhs;
+    }
+}
+
+impl Sum for ByteSize {
+    fn sum<I: Iterator<Item = ByteSize>>(iter: I) -> ByteSize {
+        iter.fold(ByteSize::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a ByteSize> for ByteSize {
+    fn sum<I: Iterator<Item = &'a ByteSize>>(iter: I) -> ByteSize {
+        iter.copied().sum()
+    }
+}
+
+/// Options chosen at compile time, which `ByteSizeWith` uses instead of `BINARY`.
+///
+/// ```rust
+/// use humansize::{ByteSize, ByteSizeWith, FormatSizeOptions, OptionsPreset, DECIMAL};
+///
+/// struct Storage;
+///
+/// impl OptionsPreset for Storage {
+///     const OPTIONS: FormatSizeOptions = DECIMAL.decimal_places(1);
+/// }
+///
+/// let size: ByteSizeWith<Storage> = ByteSize::kb(1500).with_options();
+/// assert_eq!(size.to_string(), "1.5 MB");
+/// assert_eq!("1.5 MB".parse::<ByteSizeWith<Storage>>(), Ok(size));
+/// ```
+pub trait OptionsPreset {
+    const OPTIONS: FormatSizeOptions;
+}
+
+/// The built-in presets, as `OptionsPreset`s.
+pub mod presets {
+    use super::OptionsPreset;
+    use crate::FormatSizeOptions;
+
+    macro_rules! preset {
+        ($name:ident, $preset:ident) => {
+            #[doc = concat!("The `", stringify!($preset), "` options.")]
+            #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+            pub struct $name;
+
+            impl OptionsPreset for $name {
+                const OPTIONS: FormatSizeOptions = crate::$preset;
+            }
+        };
+    }
+
+    preset!(Binary, BINARY);
+    preset!(Decimal, DECIMAL);
+    preset!(Windows, WINDOWS);
+    preset!(Jedec, JEDEC);
+}
+
+/// A `ByteSize` which displays itself, parses and (de)serializes with the options of `P` rather than `BINARY`
+/// (see `OptionsPreset`). It dereferences to the `ByteSize` it holds.
+pub struct ByteSizeWith<P: OptionsPreset = presets::Binary> {
+    size: ByteSize,
+    preset: PhantomData<fn() -> P>,
+}
+
+impl<P: OptionsPreset> ByteSizeWith<P> {
+    pub const fn new(size: ByteSize) -> ByteSizeWith<P> {
+        ByteSizeWith {
+            size,
+            preset: PhantomData,
+        }
+    }
+
+    pub const fn size(self) -> ByteSize {
+        self.size
+    }
+}
+
+impl<P: OptionsPreset> core::ops::Deref for ByteSizeWith<P> {
+    type Target = ByteSize;
+
+    fn deref(&self) -> &ByteSize {
+        &self.size
+    }
+}
+
+impl<P: OptionsPreset> fmt::Display for ByteSizeWith<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.size.display(P::OPTIONS), f)
+    }
+}
+
+impl<P: OptionsPreset> FromStr for ByteSizeWith<P> {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ByteSize::parse_with(s, P::OPTIONS).map(ByteSizeWith::new)
+    }
+}
+
+impl<P: OptionsPreset> From<ByteSize> for ByteSizeWith<P> {
+    fn from(size: ByteSize) -> Self {
+        ByteSizeWith::new(size)
+    }
+}
+
+impl<P: OptionsPreset> From<ByteSizeWith<P>> for ByteSize {
+    fn from(size: ByteSizeWith<P>) -> Self {
+        size.size
+    }
+}
+
+// Implemented by hand, as derives would require `P` to implement the traits as well
+
+impl<P: OptionsPreset> fmt::Debug for ByteSizeWith<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.size, f)
+    }
+}
+
+impl<P: OptionsPreset> Clone for ByteSizeWith<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P: OptionsPreset> Copy for ByteSizeWith<P> {}
+
+impl<P: OptionsPreset> Default for ByteSizeWith<P> {
+    fn default() -> Self {
+        ByteSizeWith::new(ByteSize::ZERO)
+    }
+}
+
+impl<P: OptionsPreset> PartialEq for ByteSizeWith<P> {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+    }
+}
+
+impl<P: OptionsPreset> Eq for ByteSizeWith<P> {}
+
+impl<P: OptionsPreset> PartialOrd for ByteSizeWith<P> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<P: OptionsPreset> Ord for ByteSizeWith<P> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.size.cmp(&other.size)
+    }
+}
+
+impl<P: OptionsPreset> Hash for ByteSizeWith<P> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+    }
+}
@@ -0,0 +1,138 @@
+//! Converts amounts between units (e.g. GB to GiB, or Gbit to MiB).
+
+use crate::{BaseUnit, FixedAt, FormatSizeOptions, ISizeFormatter, Kilo, ToF64, Unit};
+
+/// The amount of bits in one unit.
+fn bits(unit: Unit) -> u128 {
+    let bits_per_base_unit = match unit.base_unit() {
+        BaseUnit::Byte => 8,
+        BaseUnit::Bit => 1,
+    };
+    unit.multiplier() * bits_per_base_unit
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// The ratio between two units, as a reduced fraction: one `from` is `numerator / denominator` of `to`.
+fn ratio(from: Unit, to: Unit) -> (u128, u128) {
+    let (from, to) = (bits(from), bits(to));
+    let divisor = gcd(from, to);
+    (from / divisor, to / divisor)
+}
+
+/// Converts an amount of `from` units to `to` units, between bytes and bits as well.
+/// Integer amounts are converted with integer arithmetic, so that the result is only rounded once.
+///
+/// ```rust
+/// use humansize::{convert, Unit};
+///
+/// assert_eq!(convert(3u32, Unit::Gibibyte, Unit::Mebibyte), 3072.0);
+/// assert_eq!(convert(8u32, Unit::Gigabit, Unit::Megabyte), 1000.0);
+/// assert_eq!(convert(1.5, Unit::Kibibyte, Unit::Byte), 1536.0);
+/// ```
+pub fn convert(value: impl ToF64, from: Unit, to: Unit) -> f64 {
+    let (numerator, denominator) = ratio(from, to);
+    match value.to_exact() {
+        Some((negative, magnitude)) => {
+            let converted = match magnitude.checked_mul(numerator) {
+                Some(product) => {
+                    (product / denominator) as f64
+                        + (product % denominator) as f64 / denominator as f64
+                }
+                None => magnitude as f64 * numerator as f64 / denominator as f64,
+            };
+            if negative {
+                -converted
+            } else {
+                converted
+            }
+        }
+        None => value.to_f64() * numerator as f64 / denominator as f64,
+    }
+}
+
+/// Converts an amount of `from` units to `to` units, if the result is a whole number that fits in a `u128`.
+///
+/// ```rust
+/// use humansize::{convert_exact, Unit};
+///
+/// assert_eq!(convert_exact(500, Unit::Gigabyte, Unit::Megabyte), Some(500_000));
+/// assert_eq!(convert_exact(500, Unit::Gigabyte, Unit::Gibibyte), None);
+/// ```
+pub fn convert_exact(value: u128, from: Unit, to: Unit) -> Option<u128> {
+    let (numerator, denominator) = ratio(from, to);
+    // The fraction is reduced, so the product is whole only if the value is a multiple of the denominator
+    if value % denominator != 0 {
+        return None;
+    }
+    (value / denominator).checked_mul(numerator)
+}
+
+/// An amount converted to the base unit (bytes or bits) of another unit by `convert_formatter`. Whole amounts
+/// are kept exact, so that they are formatted with integer arithmetic like integer inputs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Converted {
+    value: f64,
+    exact: Option<(bool, u128)>,
+}
+
+impl Converted {
+    fn new(value: impl ToF64, from: Unit, to: Unit) -> Converted {
+        let exact = value.to_exact().and_then(|(negative, magnitude)| {
+            convert_exact(magnitude, from, to).map(|converted| (negative, converted))
+        });
+        Converted {
+            value: convert(value, from, to),
+            exact,
+        }
+    }
+}
+
+impl ToF64 for Converted {
+    fn to_f64(&self) -> f64 {
+        self.value
+    }
+
+    fn to_exact(&self) -> Option<(bool, u128)> {
+        self.exact
+    }
+}
+
+/// Formats an amount of `from` units as an amount of `to` units, with the other options unchanged.
+/// The unit labels are those of `to` (e.g. `GiB`), or the JEDEC ones if the options use them and `to` is binary.
+///
+/// ```rust
+/// use humansize::{convert_formatter, Unit, DECIMAL};
+///
+/// let formatter = convert_formatter(500u32, Unit::Gigabyte, Unit::Gibibyte, DECIMAL);
+/// assert_eq!(formatter.to_string(), "465.66 GiB");
+/// ```
+pub fn convert_formatter(
+    value: impl ToF64,
+    from: Unit,
+    to: Unit,
+    options: impl AsRef<FormatSizeOptions>,
+) -> ISizeFormatter<Converted, FormatSizeOptions> {
+    let options = options.as_ref();
+    let units = match (to.kilo(), options.units) {
+        (Kilo::Binary, Kilo::Jedec) => Kilo::Jedec,
+        (kilo, _) => kilo,
+    };
+    let options = FormatSizeOptions::from(*options)
+        .fixed_at(Some(to.scale()))
+        .kilo(to.kilo())
+        .units(units)
+        .base_unit(to.base_unit())
+        .scale_ladder(None)
+        .unit_table(None);
+
+    let base_unit = Unit::new(FixedAt::Base, Kilo::Decimal, to.base_unit());
+    ISizeFormatter::new(Converted::new(value, from, base_unit), options)
+}
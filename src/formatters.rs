@@ -1,134 +1,654 @@
-use libm::{fabs, modf, pow, round};
+use core::fmt::{self, Write};
 
-use crate::{scales, utils::f64_eq, BaseUnit, FormatSizeOptions, Kilo, ToF64, Unsigned};
+use libm::{ceil, fabs, floor, log10, modf, pow};
+
+use crate::options::MAX_PLACES;
+use crate::utils::{f64_eq, CharCounter};
+use crate::{
+    scales, Bound, FixedAt, FormatSizeOptions, FormattedSize, OptionsError, Overflow, Rounding,
+    ToF64, UnitTable, Unsigned,
+};
 
 pub struct ISizeFormatter<T: ToF64, O: AsRef<FormatSizeOptions>> {
-    value: T,
-    options: O,
+    pub(crate) value: T,
+    pub(crate) options: O,
+}
+
+/// The reasons why a size could not be formatted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FormatSizeError {
+    /// The value is `NaN` or infinite.
+    NonFinite,
+    /// The value still reaches the rollover threshold in the largest unit (yotta, or quetta with `extended_prefixes`),
+    /// so it would be displayed with more integer digits than usual (e.g. `1000000 YB`). Only reported with `Overflow::Grow`.
+    OutOfRange,
+    /// The options can't be used together.
+    InvalidOptions(OptionsError),
+}
+
+impl fmt::Display for FormatSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            FormatSizeError::NonFinite => "cannot format a size that is not finite",
+            FormatSizeError::OutOfRange => "size exceeds the largest unit",
+            FormatSizeError::InvalidOptions(err) => {
+                return write!(f, "invalid formatting options: {}", err)
+            }
+        };
+        f.write_str(msg)
+    }
 }
 
 impl<V: ToF64, O: AsRef<FormatSizeOptions>> ISizeFormatter<V, O> {
     pub fn new(value: V, options: O) -> Self {
         ISizeFormatter { value, options }
     }
-}
 
-impl<T: ToF64, O: AsRef<FormatSizeOptions>> core::fmt::Display for ISizeFormatter<T, O> {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        let opts = self.options.as_ref();
-        let divider = opts.kilo.value();
+    /// Creates a formatter, or reports why the value can't be displayed with these options.
+    /// Values that can't be displayed are accepted when the options set a `placeholder`, which is then displayed instead.
+    pub fn try_new(value: V, options: O) -> Result<Self, FormatSizeError> {
+        let formatter = ISizeFormatter { value, options };
+        formatter.check(formatter.options.as_ref())?;
+        Ok(formatter)
+    }
 
-        let mut size: f64 = self.value.to_f64();
-        let mut scale_idx = 0;
+    /// Formats the value into its parts (number, unit, suffix...), to display them separately.
+    pub fn parts(&self) -> FormattedSize {
+        self.parts_with(&self.options.as_ref().displayable())
+    }
+}
 
-        if let Some(val) = opts.fixed_at {
-            while scale_idx != val as usize {
-                size /= divider;
-                scale_idx += 1;
-            }
-        } else {
-            while fabs(size) >= divider {
-                size /= divider;
-                scale_idx += 1;
+impl<V: ToF64, O: AsRef<FormatSizeOptions>> ISizeFormatter<V, O> {
+    pub(crate) fn check(&self, opts: &FormatSizeOptions) -> Result<(), FormatSizeError> {
+        opts.validate().map_err(FormatSizeError::InvalidOptions)?;
+        if opts.placeholder.is_some() {
+            return Ok(());
+        }
+        let (scale_idx, (scaled, _)) = self.select_scale(opts);
+        match display_error(scale_idx, &scaled, opts) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
 
-                if scale_idx == scales::SCALE_LENGTH - 1 {
-                    break;
+    /// Picks the unit to display the value in, and scales the value to it.
+    pub(crate) fn select_scale(&self, opts: &FormatSizeOptions) -> (usize, (Scaled, usize)) {
+        match opts.fixed_at {
+            Some(val) => (val as usize, self.scale_to(val as usize, opts)),
+            None => {
+                // Compare the value as it would be displayed, so that rounding can't produce e.g. `1000.00 kB`.
+                // Non-finite values (`NaN`, `inf`) have no meaningful scale and are kept at the base unit.
+                let largest = largest_unit(opts);
+                let last = opts
+                    .max_unit
+                    .map_or(largest, |max| largest.min(max as usize));
+                let mut scale_idx = opts.min_unit.map_or(0, |min| last.min(min as usize));
+                let mut displayed = self.scale_to(scale_idx, opts);
+                while scale_idx < last
+                    && displayed.0.integer_part() >= rollover_threshold(scale_idx, opts)
+                    && displayed.0.integer_part().is_finite()
+                {
+                    scale_idx += 1;
+                    displayed = self.scale_to(scale_idx, opts);
                 }
+                (scale_idx, displayed)
             }
         }
+    }
 
-        let mut scale = match (opts.units, opts.long_units, opts.base_unit) {
-            (Kilo::Decimal, false, BaseUnit::Byte) => scales::SCALE_DECIMAL[scale_idx],
-            (Kilo::Decimal, true, BaseUnit::Byte) => scales::SCALE_DECIMAL_LONG[scale_idx],
-            (Kilo::Binary, false, BaseUnit::Byte) => scales::SCALE_BINARY[scale_idx],
-            (Kilo::Binary, true, BaseUnit::Byte) => scales::SCALE_BINARY_LONG[scale_idx],
-            (Kilo::Decimal, false, BaseUnit::Bit) => scales::SCALE_DECIMAL_BIT[scale_idx],
-            (Kilo::Decimal, true, BaseUnit::Bit) => scales::SCALE_DECIMAL_BIT_LONG[scale_idx],
-            (Kilo::Binary, false, BaseUnit::Bit) => scales::SCALE_BINARY_BIT[scale_idx],
-            (Kilo::Binary, true, BaseUnit::Bit) => scales::SCALE_BINARY_BIT_LONG[scale_idx],
-        };
+    /// Formats the value into the parts displayed with these options.
+    pub(crate) fn parts_with(&self, opts: &FormatSizeOptions) -> FormattedSize {
+        let (scale_idx, (scaled, places)) = self.select_scale(opts);
+        let value = self.to_scaled_f64(scale_idx, opts);
 
-        // Remove "s" from the scale if the size is 1.x
-        let (fpart, ipart) = modf(size);
-        if f64_eq(ipart, 1.0)
-            && (opts.long_units || (opts.base_unit == BaseUnit::Bit && scale_idx == 0))
-        {
-            scale = &scale[0..scale.len() - 1];
+        if let Some(placeholder) = opts.placeholder {
+            if display_error(scale_idx, &scaled, opts).is_some() {
+                let mut parts = FormattedSize::new("", "", scale_idx, value);
+                parts.placeholder = Some(placeholder);
+                return parts;
+            }
         }
 
-        let places = if f64_eq(fpart, 0.0) {
-            opts.decimal_zeroes
-        } else {
-            opts.decimal_places
+        let unit = match opts.scale_ladder {
+            // Ladder labels are displayed as they are
+            Some(ladder) => ladder[scale_idx].label,
+            None => {
+                // `< 1 KiB` is read as a single unit
+                let integer_part =
+                    if places_for(scale_idx, opts) == 0 && self.below_min_unit(&scaled, opts) {
+                        1.0
+                    } else {
+                        scaled.integer_part()
+                    };
+                unit_table(opts).label(scale_idx, integer_part)
+            }
         };
 
-        let space = if opts.space_after_value { " " } else { "" };
+        let mut parts = FormattedSize::new(unit, opts.suffix, scale_idx, value);
+        // Empty labels (e.g. bytes in `UnitTable::COMPACT`) leave no trailing space
+        parts.space = opts.space_after_value && !unit.is_empty();
+        self.set_number(&mut parts, scale_idx, scaled, places, opts);
+        parts
+    }
 
-        if let Some(sep) = opts.thousands_separator {
-            let mut buffer = [0u8; 100];
+    /// Whether the value isn't zero, but is displayed as zero in the `min_unit` it can't be scaled below.
+    fn below_min_unit(&self, scaled: &Scaled, opts: &FormatSizeOptions) -> bool {
+        let zero = match *scaled {
+            Scaled::Exact {
+                integer, fraction, ..
+            } => integer == 0 && fraction == 0,
+            Scaled::Float(size) => f64_eq(size, 0.0),
+        };
+        zero && opts.min_unit.is_some()
+            && opts.fixed_at.is_none()
+            && !f64_eq(self.value.to_f64(), 0.0)
+    }
+
+    /// Sets the number of the parts to the value scaled to the unit at `scale_idx`.
+    pub(crate) fn set_number(
+        &self,
+        parts: &mut FormattedSize,
+        scale_idx: usize,
+        scaled: Scaled,
+        places: usize,
+        opts: &FormatSizeOptions,
+    ) {
+        parts.separator = opts.thousands_separator;
+        if self.below_min_unit(&scaled, opts) {
+            // The smallest amount the unit can display, e.g. `< 0.01 KiB`
+            let negative = self.value.to_f64() < 0.0;
+            parts.bound = Some(if negative {
+                Bound::GreaterThan
+            } else {
+                Bound::LessThan
+            });
+            match places_for(scale_idx, opts) {
+                0 => parts.set_exact(negative, 1, 0, 0),
+                places => parts.set_exact(negative, 0, 1, places),
+            }
+            return;
+        }
 
-            let mut cursor = buffer.len();
-            let is_negative = size.is_sign_negative();
+        let overflowing =
+            scaled.integer_part().is_finite() && beyond_largest_unit(scale_idx, &scaled, opts);
+        if overflowing && opts.overflow == Overflow::Saturate {
+            let negative = self.value.to_f64() < 0.0;
+            parts.bound = Some(if negative {
+                Bound::LessThan
+            } else {
+                Bound::GreaterThan
+            });
+            let max = ceil(rollover_threshold(scale_idx, opts)) - 1.0;
+            parts.set_exact(negative, max as u128, 0, 0);
+            return;
+        }
 
-            // Handle the fractional part
-            let mut fraction = round(fpart * pow(10f64, places as f64)) as u64;
-            for _ in 0..places {
-                if cursor == 0 {
-                    panic!("Buffer too small!");
-                }
-                cursor -= 1;
-                buffer[cursor] = b'0' + (fraction % 10) as u8;
-                fraction /= 10;
+        let unit_opts = &opts.decimal_places(places_for(scale_idx, opts));
+        match (scaled, opts.overflow) {
+            (_, Overflow::Scientific) if overflowing => {
+                set_exponent(parts, self.to_scaled_f64(scale_idx, opts), 1, unit_opts)
             }
+            (_, Overflow::Engineering) if overflowing => {
+                set_exponent(parts, self.to_scaled_f64(scale_idx, opts), 3, unit_opts)
+            }
+            (
+                Scaled::Exact {
+                    negative,
+                    integer,
+                    fraction,
+                },
+                _,
+            ) => parts.set_exact(negative, integer, fraction, places),
+            (Scaled::Float(size), _) => parts.set_float(size, places),
+        }
+        if let Some(min) = min_places(places_for(scale_idx, opts), opts) {
+            parts.set_min_fraction_digits(min);
+        }
+    }
+}
 
-            if places > 0 {
-                if cursor == 0 {
-                    panic!("Buffer too small!");
-                }
-                cursor -= 1;
-                buffer[cursor] = b'.';
+impl<V: ToF64, O: AsRef<FormatSizeOptions>> ISizeFormatter<V, O> {
+    /// Scales the value to the unit at `scale_idx` and rounds it for display.
+    /// Integers are scaled exactly whenever possible, other values go through `f64`.
+    fn scale_to(&self, scale_idx: usize, opts: &FormatSizeOptions) -> (Scaled, usize) {
+        let opts = &opts.decimal_places(places_for(scale_idx, opts));
+        if let Some(exact) = self.value.to_exact().and_then(|(negative, magnitude)| {
+            exact_for_display(negative, magnitude, scale_idx, opts)
+        }) {
+            return exact;
+        }
+
+        let base_unit = match opts.scale_ladder {
+            Some(ladder) => ladder[scale_idx].multiplier == 1,
+            None => scale_idx == 0,
+        };
+        let (rounded, places) =
+            round_for_display(self.to_scaled_f64(scale_idx, opts), base_unit, opts);
+        (Scaled::Float(rounded), places)
+    }
+
+    pub(crate) fn to_scaled_f64(&self, scale_idx: usize, opts: &FormatSizeOptions) -> f64 {
+        let mut size = self.value.to_f64();
+        if let Some(ladder) = opts.scale_ladder {
+            return size / ladder[scale_idx].multiplier as f64;
+        }
+        for _ in 0..scale_idx {
+            size /= opts.kilo.value();
+        }
+        size
+    }
+}
+
+/// The decimal places to display in the unit at `scale_idx`.
+fn places_for(scale_idx: usize, opts: &FormatSizeOptions) -> usize {
+    match opts.unit_decimal_places {
+        Some(places) if !places.is_empty() => places[scale_idx.min(places.len() - 1)],
+        _ => opts.decimal_places,
+    }
+}
+
+/// The most decimal places to display, whichever option chooses them.
+fn max_places(opts: &FormatSizeOptions) -> usize {
+    opts.max_fraction_digits.unwrap_or(usize::MAX)
+}
+
+/// The least decimal places to display along with at most `places` (those of the unit, unless significant digits
+/// choose them), never more than the most displayed.
+pub(crate) fn min_places(places: usize, opts: &FormatSizeOptions) -> Option<usize> {
+    let most = match opts.significant_digits {
+        Some(_) => max_places(opts),
+        None => places.min(max_places(opts)),
+    };
+    opts.min_fraction_digits.map(|min| min.min(most))
+}
+
+fn unit_table(opts: &FormatSizeOptions) -> UnitTable {
+    opts.unit_table
+        .unwrap_or_else(|| UnitTable::standard(opts.units, opts.long_units, opts.base_unit))
+}
+
+/// The displayed value from which to switch from the unit at `scale_idx` to the next one.
+/// The last step of a ladder has no such threshold.
+fn rollover_threshold(scale_idx: usize, opts: &FormatSizeOptions) -> f64 {
+    match opts.scale_ladder {
+        Some(ladder) => match ladder.get(scale_idx + 1) {
+            Some(next) => next.multiplier as f64 / ladder[scale_idx].multiplier as f64,
+            None => f64::INFINITY,
+        },
+        None => opts
+            .rollover_threshold
+            .map_or(opts.kilo.value().min(1000.0), f64::from),
+    }
+}
+
+/// The index of the largest unit automatic scaling can reach, before `max_unit` is applied.
+fn largest_unit(opts: &FormatSizeOptions) -> usize {
+    match opts.scale_ladder {
+        Some(ladder) => ladder.len() - 1,
+        None => {
+            let last = if opts.extended_prefixes {
+                scales::SCALE_LENGTH - 1
+            } else {
+                FixedAt::Yotta as usize
+            };
+            unit_table(opts).labels.len().min(last + 1) - 1
+        }
+    }
+}
+
+/// Whether the value still reaches the threshold once scaled to the largest unit.
+fn beyond_largest_unit(scale_idx: usize, scaled: &Scaled, opts: &FormatSizeOptions) -> bool {
+    opts.fixed_at.is_none()
+        && scale_idx >= largest_unit(opts)
+        && scaled.integer_part() >= rollover_threshold(scale_idx, opts)
+}
+
+/// Reports a value that can't be properly displayed once scaled: not finite, or beyond the largest unit
+/// when the overflow policy is to grow.
+pub(crate) fn display_error(
+    scale_idx: usize,
+    scaled: &Scaled,
+    opts: &FormatSizeOptions,
+) -> Option<FormatSizeError> {
+    if !scaled.integer_part().is_finite() {
+        Some(FormatSizeError::NonFinite)
+    } else if opts.overflow == Overflow::Grow && beyond_largest_unit(scale_idx, scaled, opts) {
+        Some(FormatSizeError::OutOfRange)
+    } else {
+        None
+    }
+}
+
+/// Sets the number of the parts to the size as a mantissa times a power of ten which is a multiple of `step`:
+/// 1 for scientific notation, 3 for engineering notation.
+fn set_exponent(parts: &mut FormattedSize, size: f64, step: i32, opts: &FormatSizeOptions) {
+    let magnitude = floor(log10(fabs(size))) as i32;
+    let mut exponent = magnitude - magnitude.rem_euclid(step);
+    let mut mantissa = round_for_display(size / pow(10f64, exponent as f64), false, opts);
+
+    // Rounding may carry into the next power (e.g. 9.996e3 -> 10.00e3)
+    if fabs(mantissa.0) >= pow(10f64, step as f64) {
+        exponent += step;
+        mantissa = round_for_display(size / pow(10f64, exponent as f64), false, opts);
+    }
+
+    parts.set_float(mantissa.0, mantissa.1);
+    parts.exponent = Some(exponent);
+}
+
+/// A size scaled to a unit and rounded for display.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Scaled {
+    /// The exact result of integer arithmetic, the fraction holding the decimal digits to display.
+    Exact {
+        negative: bool,
+        integer: u128,
+        fraction: u128,
+    },
+    /// A rounded float.
+    Float(f64),
+}
+
+impl Scaled {
+    fn integer_part(&self) -> f64 {
+        match *self {
+            Scaled::Exact { integer, .. } => integer as f64,
+            Scaled::Float(size) => fabs(modf(size).1),
+        }
+    }
+}
+
+/// The most decimal places the exact path can compute, as they are accumulated in a `u128`.
+const MAX_EXACT_PLACES: usize = 38;
+
+/// Divides `magnitude` by `divisor` and rounds the result to `places` decimal places.
+/// Returns the integer part, the decimals, and whether the division was exact.
+fn round_exact(
+    negative: bool,
+    magnitude: u128,
+    divisor: u128,
+    places: usize,
+    rounding: Rounding,
+) -> Option<(u128, u128, bool)> {
+    if places > MAX_EXACT_PLACES {
+        return None;
+    }
+
+    let mut integer = magnitude / divisor;
+    let mut remainder = magnitude % divisor;
+    let whole = remainder == 0;
+
+    // Long division, one decimal at a time
+    let mut fraction: u128 = 0;
+    for _ in 0..places {
+        remainder = remainder.checked_mul(10)?;
+        fraction = fraction * 10 + remainder / divisor;
+        remainder %= divisor;
+    }
+
+    let round_up = remainder != 0 && {
+        let twice = remainder.checked_mul(2)?;
+        let last_digit = if places > 0 { fraction } else { integer };
+        match rounding {
+            Rounding::HalfUp => twice >= divisor,
+            Rounding::HalfEven => twice > divisor || (twice == divisor && last_digit % 2 == 1),
+            Rounding::Floor => negative,
+            Rounding::Ceil => !negative,
+            Rounding::Truncate => false,
+        }
+    };
+
+    if round_up {
+        fraction += 1;
+        if fraction == 10u128.pow(places as u32) {
+            fraction = 0;
+            integer = integer.checked_add(1)?;
+        }
+    }
+
+    Some((integer, fraction, whole))
+}
+
+/// The exact counterpart of `round_for_display`, returns `None` when the computation doesn't fit in a `u128`.
+fn exact_for_display(
+    negative: bool,
+    magnitude: u128,
+    scale_idx: usize,
+    opts: &FormatSizeOptions,
+) -> Option<(Scaled, usize)> {
+    let divisor = match opts.scale_ladder {
+        Some(ladder) => ladder[scale_idx].multiplier,
+        None => (opts.kilo.value() as u128).checked_pow(scale_idx as u32)?,
+    };
+
+    let places = match opts.significant_digits {
+        Some(digits) => {
+            let integer = magnitude / divisor;
+            let order = if integer > 0 || magnitude == 0 {
+                count_digits(integer) as i64 - 1
+            } else {
+                floor(log10(magnitude as f64 / divisor as f64)) as i64
+            };
+            let places = (digits as i64 - 1 - order).max(0) as usize;
+
+            // Rounding may carry into a new digit (e.g. 9.996 -> 10.0), which then counts as significant
+            let (integer, fraction, _) =
+                round_exact(negative, magnitude, divisor, places, opts.rounding)?;
+            let carried = if order >= 0 {
+                count_digits(integer) as i64 > order + 1
+            } else {
+                integer > 0 || count_digits(fraction) as usize > digits
+            };
+            if places > 0 && carried {
+                places - 1
+            } else {
+                places
             }
+        }
+        None => opts.decimal_places,
+    }
+    .min(max_places(opts));
 
-            // Handle the integer part
-            let mut integer_part = fabs(ipart);
-            let mut digit_count = 0;
-            loop {
-                if cursor == 0 {
-                    panic!("Buffer too small!");
-                }
+    let (integer, fraction, whole) =
+        round_exact(negative, magnitude, divisor, places, opts.rounding)?;
+    // Significant digits keep their padding (e.g. `10.0 KiB`), except in whole amounts of the base unit
+    let padded = opts.significant_digits.is_some() && divisor != 1;
+    let places = if fraction == 0 && (places > 0 || whole) && !padded {
+        opts.decimal_zeroes.min(max_places(opts))
+    } else {
+        places
+    };
 
-                if digit_count == 3 {
-                    cursor -= 1;
-                    buffer[cursor] = sep as u8;
-                    digit_count = 0;
-                }
+    Some((
+        Scaled::Exact {
+            negative,
+            integer,
+            fraction,
+        },
+        places,
+    ))
+}
+
+pub(crate) fn count_digits(mut n: u128) -> u32 {
+    let mut digits = 1;
+    while n >= 10 {
+        n /= 10;
+        digits += 1;
+    }
+    digits
+}
 
-                cursor -= 1;
-                buffer[cursor] = b'0' + (integer_part % 10f64) as u8;
-                integer_part /= 10f64;
-                digit_count += 1;
+/// Writes a formatted number, inserting the separator between groups of three digits of its integer part.
+/// The digits are streamed to the writer, so numbers of any length can be written without a buffer.
+pub(crate) fn write_separated<W: Write + ?Sized>(
+    f: &mut W,
+    sep: Option<char>,
+    number: fmt::Arguments,
+) -> fmt::Result {
+    let sep = match sep {
+        Some(sep) => sep,
+        None => return f.write_fmt(number),
+    };
 
-                if integer_part < 1f64 {
-                    break;
-                }
+    let mut counter = IntegerDigits {
+        count: 0,
+        done: false,
+    };
+    counter.write_fmt(number)?;
+
+    SeparatedDigits {
+        inner: f,
+        sep,
+        remaining: counter.count,
+    }
+    .write_fmt(number)
+}
+
+/// Counts the digits of the integer part of a formatted number.
+struct IntegerDigits {
+    count: usize,
+    done: bool,
+}
+
+impl Write for IntegerDigits {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c.is_ascii_digit() && !self.done {
+                self.count += 1;
+            } else if self.count > 0 {
+                self.done = true;
             }
+        }
+        Ok(())
+    }
+}
+
+/// Forwards a formatted number, adding a separator after each group of three digits of its integer part.
+struct SeparatedDigits<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    sep: char,
+    remaining: usize,
+}
 
-            // Add the negative sign if necessary
-            if is_negative {
-                if cursor == 0 {
-                    panic!("Buffer too small!");
+impl<'a, W: Write + ?Sized> Write for SeparatedDigits<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            self.inner.write_char(c)?;
+            if c.is_ascii_digit() && self.remaining > 0 {
+                self.remaining -= 1;
+                if self.remaining > 0 && self.remaining % 3 == 0 {
+                    self.inner.write_char(self.sep)?;
                 }
-                cursor -= 1;
-                buffer[cursor] = b'-';
             }
-
-            // Return a &str pointing to the formatted result
-            let formatted = core::str::from_utf8(&buffer[cursor..]).unwrap();
-            return write!(f, "{}{}{}{}", formatted, space, scale, opts.suffix);
         }
+        Ok(())
+    }
+}
+
+/// Returns the amount of decimal places needed to display `digits` significant digits of `size`,
+/// without ever rounding its integer part.
+fn significant_places(size: f64, digits: usize, rounding: Rounding) -> usize {
+    let size = fabs(size);
+    if size == 0.0 {
+        return 0;
+    }
+
+    let magnitude = floor(log10(size)) as i64;
+    let places = (digits as i64 - 1 - magnitude).max(0);
+
+    // Rounding may carry into a new integer digit (e.g. 9.996 -> 10.0), which then counts as significant
+    if places > 0 && rounding.round_to(size, places as usize) >= pow(10f64, (magnitude + 1) as f64)
+    {
+        return (places - 1) as usize;
+    }
+    places as usize
+}
+
+/// Rounds the size as it will be displayed, and returns it along with the amount of decimal places to display.
+/// `decimal_zeroes` applies when the rounded value has no fractional part, unless significant digits are displayed:
+/// these keep their padding (e.g. `10.0 KiB`), except in whole amounts of the base unit (`base_unit` being set when
+/// `size` is an amount of it).
+pub(crate) fn round_for_display(
+    size: f64,
+    base_unit: bool,
+    opts: &FormatSizeOptions,
+) -> (f64, usize) {
+    let places = match opts.significant_digits {
+        Some(digits) => significant_places(size, digits, opts.rounding),
+        None => opts.decimal_places,
+    }
+    .min(max_places(opts));
+
+    let rounded = opts.rounding.round_to(size, places);
+    let (fpart, _) = modf(rounded);
+
+    let whole = f64_eq(modf(size).0, 0.0);
+    let padded = opts.significant_digits.is_some() && !(base_unit && whole);
+    // Values with a fractional part still keep no decimals if none were asked for
+    if f64_eq(fpart, 0.0) && (places > 0 || whole) && !padded {
+        (rounded, opts.decimal_zeroes.min(max_places(opts)))
+    } else {
+        (rounded, places)
+    }
+}
+
+/// Honors the formatter's flags: width, fill and alignment pad the output (right-aligned by default, like numbers),
+/// the precision overrides `decimal_places` (up to 38), and the alternate flag (`{:#}`) switches to long units.
+impl<T: ToF64, O: AsRef<FormatSizeOptions>> fmt::Display for ISizeFormatter<T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opts = options_with_flags(f, self.options.as_ref());
+        fmt::Display::fmt(&self.parts_with(&opts), f)
+    }
+}
+
+/// Applies the precision and alternate flags of the formatter to the options.
+pub(crate) fn options_with_flags(
+    f: &fmt::Formatter,
+    options: &FormatSizeOptions,
+) -> FormatSizeOptions {
+    let mut opts = options.displayable();
+    if let Some(places) = f.precision() {
+        opts.decimal_places = places.min(MAX_PLACES);
+        opts.unit_decimal_places = None;
+        opts.significant_digits = None;
+        opts.max_fraction_digits = None;
+    }
+    if f.alternate() {
+        opts.long_units = true;
+    }
+    opts
+}
 
-        write!(f, "{:.*}{}{}{}", places, size, space, scale, opts.suffix)
+/// Pads the output of `write` according to the width, fill and alignment flags of the formatter.
+pub(crate) fn write_padded(
+    f: &mut fmt::Formatter,
+    write: impl Fn(&mut dyn Write) -> fmt::Result,
+) -> fmt::Result {
+    let width = match f.width() {
+        Some(width) => width,
+        None => return write(f),
+    };
+
+    let mut counter = CharCounter(0);
+    write(&mut counter)?;
+
+    let padding = width.saturating_sub(counter.0);
+    let (before, after) = match f.align() {
+        Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+        Some(fmt::Alignment::Right) | None => (padding, 0),
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
     }
+    write(f)?;
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
 }
 
 impl<'a, U: ToF64 + Unsigned + Copy, O: AsRef<FormatSizeOptions>> From<&'a SizeFormatter<U, O>>
@@ -153,10 +673,24 @@ impl<V: ToF64 + Unsigned, O: AsRef<FormatSizeOptions>> SizeFormatter<V, O> {
     }
 }
 
-impl<T: ToF64 + Unsigned + Copy, O: AsRef<FormatSizeOptions> + Copy> core::fmt::Display
+impl<V: ToF64 + Unsigned + Copy, O: AsRef<FormatSizeOptions>> SizeFormatter<V, O> {
+    /// Creates a formatter, or reports why the value can't be displayed with these options.
+    /// Values that can't be displayed are accepted when the options set a `placeholder`, which is then displayed instead.
+    pub fn try_new(value: V, options: O) -> Result<Self, FormatSizeError> {
+        ISizeFormatter::try_new(value, &options)?;
+        Ok(SizeFormatter { value, options })
+    }
+
+    /// Formats the value into its parts (number, unit, suffix...), to display them separately.
+    pub fn parts(&self) -> FormattedSize {
+        ISizeFormatter::from(self).parts()
+    }
+}
+
+impl<T: ToF64 + Unsigned + Copy, O: AsRef<FormatSizeOptions> + Copy> fmt::Display
     for SizeFormatter<T, O>
 {
-    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-        write!(f, "{}", ISizeFormatter::from(self))
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&ISizeFormatter::from(self), f)
     }
 }
@@ -17,7 +17,7 @@ Add humansize as a dependency to your project's `cargo.toml`:
 ```toml
 [dependencies]
 ...
-humansize = "2.0.0"
+humansize = "3.0.0"
 ```
 
 ### ... to easily format a size:
@@ -57,7 +57,7 @@ Specify the `no_alloc` feature flag in your project's `cargo.toml`:
 ```toml
 [dependencies]
 ...
-humansize = { version = "2.0.0", features = ["no_alloc"] }
+humansize = { version = "3.0.0", features = ["no_alloc"] }
 ```
 This excludes all allocating code from compilation. You may now use the library's internal `SizeFormatter` struct, which implements `core::fmt::display` so that you can `write!` it to a custom buffer of your choice:
 ```rust
@@ -66,6 +66,15 @@ use humansize::{SizeFormatter, DECIMAL};
 let formatter = SizeFormatter::new(1_000_000usize, DECIMAL);
 assert_eq!(format!("{}", formatter), "1 MB");
 ```
+The formatters honor the usual formatting flags: width, fill and alignment pad the output, the precision overrides the amount of decimal places, and the alternate flag (`{:#}`) switches to long units:
+```rust
+use humansize::{SizeFormatter, BINARY};
+
+let formatter = SizeFormatter::new(1536u32, BINARY);
+assert_eq!(format!("{:>10}", formatter), "  1.50 KiB");
+assert_eq!(format!("{:.1}", formatter), "1.5 KiB");
+assert_eq!(format!("{:#}", formatter), "1.50 Kibibyte");
+```
 ### ... with the `impl` style API:
 For stylistic reasons, you may prefer to use the impl-style API of earlier versions of the crate.
 To do so, specify the `impl-style` feature flag in your project's `cargo.toml`:
@@ -73,7 +82,7 @@ To do so, specify the `impl-style` feature flag in your project's `cargo.toml`:
 ```toml
 [dependencies]
 ...
-humansize = { version = "2.0.0", features = ["impl_style"] }
+humansize = { version = "3.0.0", features = ["impl_style"] }
 ```
 Enabling this feature makes two methods available:
 - `format_size` on unsigned integers types
@@ -87,13 +96,180 @@ assert_eq!(1_000_000u64.format_size(DECIMAL), "1 MB");
 assert_eq!((-1_000_000).format_size_i(DECIMAL), "-1 MB");
 ```
 ### ... to further customize the output:
-Humansize exports three default option sets:
+Humansize exports four default option sets:
 * `Decimal`: kilo = 1000, unit format is `XB`.
 * `Binary`: kilo = 1024, unit format is `XiB`.
 * `WINDOWS` (Windows): kilo = 1024, unit format is `XB`.
+* `JEDEC`: kilo = 1024, unit format is `XB` with the JEDEC labels (`KB` rather than `kB`, and `Kilobytes (1024)` with long units), as used for memory modules.
 
 The formatting can be further customized by providing providing your own option set. See the documentation of the `FormatSizeOptions` struct to see all the addressable parameters, and [this example](examples/custom_options.rs) for its usage.
 
+Units go up to yotta (`YB`, `YiB`) by default. Values beyond it are displayed with more integer digits in that unit, or according to the `overflow` option: in scientific (`1.23e3 YB`) or engineering notation, or as a bound (`> 999 YB`).
+
+Sizes can also be scaled by any base with `Kilo::Custom` (e.g. 512 for blocks), or along an explicit ladder of units with the `scale_ladder` option:
+```rust
+use humansize::{format_size, FormatSizeOptions, ScaleStep, DECIMAL};
+
+const LADDER: &[ScaleStep] = &[ScaleStep::new(1, "B"), ScaleStep::new(1_000_000, "MB")];
+let options = FormatSizeOptions::from(DECIMAL).scale_ladder(Some(LADDER));
+assert_eq!(format_size(500_000u32, options), "500000 B");
+assert_eq!(format_size(1_500_000u32, options), "1.50 MB");
+```
+Different units can display different amounts of decimal places with the `unit_decimal_places` option:
+```rust
+use humansize::{format_size, BINARY};
+
+let options = BINARY.unit_decimal_places(Some(&[0, 0, 1, 2]));
+assert_eq!(format_size(512u32, options), "512 B");
+assert_eq!(format_size(1_342_177_280u32, options), "1.25 GiB");
+```
+The `min_fraction_digits` and `max_fraction_digits` options display a varying amount of decimal places, between the two (`decimal_places` being the most by default), by removing trailing zeros:
+```rust
+use humansize::{format_size, BINARY};
+
+let options = BINARY.min_fraction_digits(Some(0));
+assert_eq!(format_size(1536u32, options), "1.5 KiB");
+assert_eq!(format_size(1280u32, options), "1.25 KiB");
+assert_eq!(format_size(1024u32, BINARY.min_fraction_digits(Some(1))), "1.0 KiB");
+assert_eq!(format_size(1280u32, options.max_fraction_digits(Some(1))), "1.2 KiB");
+```
+The automatically chosen unit can be bounded with the `min_unit` and `max_unit` options, values too small for the smallest unit being displayed as a bound:
+```rust
+use humansize::{format_size, FixedAt, BINARY};
+
+let listing = BINARY.min_unit(Some(FixedAt::Kilo));
+assert_eq!(format_size(5u32, listing), "< 0.01 KiB");
+assert_eq!(format_size(2_199_023_255_552u64, BINARY.max_unit(Some(FixedAt::Giga))), "2048 GiB");
+```
+`scale_for` returns the unit a size would be displayed in, and the value in that unit, without formatting it:
+```rust
+use humansize::{FixedAt, SizeScale, BINARY};
+
+assert_eq!(BINARY.scale_for(1536u32), SizeScale { scale: 1, fixed_at: Some(FixedAt::Kilo), value: 1.5 });
+assert_eq!(BINARY.max_unit(Some(FixedAt::Kilo)).scale_for(2_097_152u32).value, 2048.0);
+```
+The unit labels can be replaced with a `UnitTable`, either one of the built-in tables (such as `UnitTable::COMPACT`, the single-letter labels of `ls -h`) or your own:
+```rust
+use humansize::{format_size, FormatSizeOptions, UnitTable, BINARY, DECIMAL};
+
+let ls = FormatSizeOptions::from(BINARY).unit_table(Some(UnitTable::COMPACT)).space_after_value(false).decimal_places(1);
+assert_eq!(format_size(4200u32, ls), "4.1K");
+
+const OCTETS: UnitTable = UnitTable::new(&["o", "Ko", "Mo", "Go", "To"], false);
+assert_eq!(format_size(1_500_000u32, DECIMAL.unit_table(Some(OCTETS))), "1.50 Mo");
+```
+The `extended_prefixes` option adds the ronna (`RB`, `RiB`) and quetta (`QB`, `QiB`) prefixes of the 2022 SI revision.
+
+Options built at runtime (e.g. from a configuration file) can be checked with `FormatSizeOptions::validate`, or built with `FormatSizeOptionsBuilder`, whose `build` method reports conflicting or unsupported combinations as an `OptionsError`:
+```rust
+use humansize::{FormatSizeOptionsBuilder, OptionsError, DECIMAL};
+
+let options = FormatSizeOptionsBuilder::new(DECIMAL).thousands_separator(Some('.')).build();
+assert_eq!(options.unwrap_err(), OptionsError::AmbiguousSeparator);
+```
+
+### ... to handle values that can't be displayed:
+Float inputs may be `NaN` or infinite, and very large values may exceed the largest unit.
+`try_format_size` and `try_format_size_i` report these cases, as well as options which can't be used together, with a `FormatSizeError`.
+Alternatively, the `placeholder` option sets a string to display in place of such values:
+```rust
+use humansize::{format_size_i, try_format_size_i, FormatSizeError, FormatSizeOptions, BINARY};
+
+assert_eq!(try_format_size_i(f64::NAN, BINARY), Err(FormatSizeError::NonFinite));
+
+let options = FormatSizeOptions::from(BINARY).placeholder(Some("n/a"));
+assert_eq!(format_size_i(f64::INFINITY, options), "n/a");
+```
+
+### ... to style the number and the unit separately:
+`SizeFormatter::parts` and `ISizeFormatter::parts` return a `FormattedSize`, holding the sign, digits, unit and suffix the formatter would display, as well as the chosen unit and the scaled value. With the `serde` feature, it can be serialized for front-ends:
+```rust
+use humansize::{SizeFormatter, BINARY};
+
+let parts = SizeFormatter::new(1_610_612_736u64, BINARY).parts();
+assert_eq!(parts.integer().to_string(), "1");
+assert_eq!(parts.fraction().to_string(), "50");
+assert_eq!(parts.unit, "GiB");
+assert_eq!(parts.to_string(), "1.50 GiB");
+```
+
+### ... to format other quantities:
+`QuantityFormatter` (and the allocating `format_quantity`) applies the same formatting to amounts of any unit, using SI prefixes from pico (`p`) to yotta (`Y`):
+```rust
+use humansize::{format_quantity, DECIMAL};
+
+assert_eq!(format_quantity(2_400_000_000u64, "Hz", DECIMAL), "2.40 GHz");
+assert_eq!(format_quantity(0.0025, "W", DECIMAL), "2.50 mW");
+```
+
+### ... to parse a size:
+`parse_size` performs the inverse operation, turning a human-readable size back into an amount of bytes.
+It understands every unit the crate can produce and lets you pick the numeric type of the result:
+```rust
+use humansize::{parse_size, ParseSizeOptions, WINDOWS};
+
+assert_eq!(parse_size::<u64>("1.5 GiB", ParseSizeOptions::new()), Ok(1_610_612_736));
+assert_eq!(parse_size::<u64>("2 Megabytes", ParseSizeOptions::new()), Ok(2_000_000));
+
+// Passing a set of formatting options parses sizes the way they would be formatted with them
+assert_eq!(parse_size::<u64>("1 kB", WINDOWS), Ok(1024));
+```
+
+### ... to work with units:
+`Unit` lists the units the crate displays, with their labels and sizes, and converts from `FixedAt` and from unit labels:
+```rust
+use humansize::{BaseUnit, FixedAt, Kilo, Unit};
+
+assert_eq!(Unit::Mebibyte.multiplier(), 1_048_576);
+assert_eq!((Unit::Gigabyte.symbol(), Unit::Gigabyte.long_name()), ("GB", "Gigabyte"));
+assert_eq!("KiB".parse::<Unit>(), Ok(Unit::Kibibyte));
+assert_eq!(Unit::new(FixedAt::Tera, Kilo::Decimal, BaseUnit::Bit), Unit::Terabit);
+```
+Amounts can be converted between units, bytes and bits included, with `convert` (or `convert_exact` for whole results) and formatted in the target unit with `convert_formatter` or `format_converted`:
+```rust
+use humansize::{convert_exact, format_converted, Unit, DECIMAL};
+
+assert_eq!(convert_exact(8, Unit::Gigabit, Unit::Megabyte), Some(1000));
+assert_eq!(format_converted(500u32, Unit::Gigabyte, Unit::Gibibyte, DECIMAL), "465.66 GiB");
+```
+
+### ... to store sizes:
+`ByteSize` is a value type for amounts of bytes. It supports arithmetic and comparisons, displays itself through `SizeFormatter`, and parses from the same strings `parse_size` accepts:
+```rust
+use humansize::{ByteSize, DECIMAL};
+
+let total: ByteSize = [ByteSize::kib(512), ByteSize::mib(1)].iter().sum();
+assert_eq!(total.to_string(), "1.50 MiB");
+assert_eq!(total.display(DECIMAL).to_string(), "1.57 MB");
+assert_eq!("1.5 MiB".parse::<ByteSize>(), Ok(total));
+```
+`Display` and `FromStr` use `BINARY`, other options are passed to `ByteSize::display` and `ByteSize::parse_with`. `ByteSizeWith` displays, parses and (de)serializes sizes with the options of an `OptionsPreset` instead, either one of the `presets` or your own:
+```rust
+use humansize::{presets, ByteSize, ByteSizeWith};
+
+let size: ByteSizeWith<presets::Decimal> = ByteSize::kb(1500).with_options();
+assert_eq!(size.to_string(), "1.50 MB");
+assert_eq!("1.5 MB".parse(), Ok(size));
+```
+
+### ... to read and write sizes in configuration files:
+Specify the `serde` feature flag in your project's `cargo.toml`:
+```toml
+[dependencies]
+...
+humansize = { version = "3.0.0", features = ["serde"] }
+```
+The `humansize::serde` module then provides one module per preset, to be used with `#[serde(with = "...")]` on integer fields.
+Sizes are written as formatted strings, and read back from either an integer or a human-readable string:
+```ignore
+#[derive(Serialize, Deserialize)]
+struct Config {
+    #[serde(with = "humansize::serde::binary")]
+    max_upload: u64, // max_upload = "512 MiB"
+}
+```
+`FormatSizeOptions` can also be deserialized, either from the name of a preset (`"binary"`, `"decimal"`, `"windows"`, `"jedec"`) or from a map of fields overriding one (e.g. `{ preset = "binary", decimal_places = 1 }`).
+
 ### ... to accept negative values:
 The solutions presented above only accept unsigned integer types as input (`usize`, `8`, `u16`, `u32` and `u64`). If however accepting negative values is correct for your application, a signed alternative exists for each of them that will accept signed integer types, and format them accordingly if negative:
 
@@ -125,14 +301,33 @@ extern crate alloc;
 extern crate libm;
 
 mod options;
-pub use options::{BaseUnit, FixedAt, FormatSizeOptions, Kilo, BINARY, DECIMAL, WINDOWS};
+pub use options::{
+    BaseUnit, FixedAt, FormatSizeOptions, FormatSizeOptionsBuilder, Kilo, OptionsError, Overflow,
+    Rounding, ScaleStep, SizeScale, BINARY, DECIMAL, JEDEC, WINDOWS,
+};
 
 mod numeric_traits;
-pub use numeric_traits::{Signed, ToF64, Unsigned};
+pub use numeric_traits::{FromSize, Signed, ToF64, Unsigned};
 
 mod scales;
+pub use scales::UnitTable;
 mod utils;
 
+mod unit;
+pub use unit::Unit;
+
+mod convert;
+pub use convert::{convert, convert_exact, convert_formatter, Converted};
+
+mod parse;
+pub use parse::{parse_size, ParseSizeError, ParseSizeOptions};
+
+mod byte_size;
+pub use byte_size::{presets, ByteSize, ByteSizeWith, OptionsPreset};
+
+#[cfg(feature = "serde")]
+pub mod serde;
+
 #[cfg(not(feature = "no_alloc"))]
 mod allocating;
 #[cfg(not(feature = "no_alloc"))]
@@ -144,4 +339,10 @@ mod impl_style;
 pub use impl_style::{FormatSize, FormatSizeI};
 
 mod formatters;
-pub use formatters::{ISizeFormatter, SizeFormatter};
+pub use formatters::{FormatSizeError, ISizeFormatter, SizeFormatter};
+
+mod parts;
+pub use parts::{Bound, Digits, FormattedSize, NonFinite};
+
+mod quantity;
+pub use quantity::QuantityFormatter;
@@ -1,5 +1,10 @@
 pub trait ToF64 {
     fn to_f64(&self) -> f64;
+
+    /// Returns the sign and magnitude of integer values, so that they can be formatted without losing precision.
+    fn to_exact(&self) -> Option<(bool, u128)> {
+        None
+    }
 }
 
 macro_rules! impl_to_f64 {
@@ -12,7 +17,39 @@ macro_rules! impl_to_f64 {
   )*)
 }
 
-impl_to_f64!(for usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128 f32 f64);
+impl_to_f64!(for f32 f64);
+
+macro_rules! impl_to_f64_unsigned {
+  (for $($t:ty)*) => ($(
+      impl ToF64 for $t {
+          fn to_f64(&self) -> f64 {
+              *self as f64
+          }
+
+          fn to_exact(&self) -> Option<(bool, u128)> {
+              Some((false, *self as u128))
+          }
+      }
+  )*)
+}
+
+impl_to_f64_unsigned!(for usize u8 u16 u32 u64 u128);
+
+macro_rules! impl_to_f64_signed {
+  (for $($t:ty)*) => ($(
+      impl ToF64 for $t {
+          fn to_f64(&self) -> f64 {
+              *self as f64
+          }
+
+          fn to_exact(&self) -> Option<(bool, u128)> {
+              Some((*self < 0, self.unsigned_abs() as u128))
+          }
+      }
+  )*)
+}
+
+impl_to_f64_signed!(for isize i8 i16 i32 i64 i128);
 
 pub trait Unsigned {}
 
@@ -33,3 +70,35 @@ macro_rules! impl_unsigned {
 }
 
 impl_unsigned!(for isize i8 i16 i32 i64 i128);
+
+/// Types that a parsed size can be converted into.
+pub trait FromSize: Sized {
+    /// Builds the value from the whole and fractional (`0.0 <= fraction < 1.0`) parts of a size.
+    /// Returns `None` if it does not fit.
+    fn from_size(whole: u128, fraction: f64) -> Option<Self>;
+}
+
+macro_rules! impl_from_size_int {
+  (for $($t:ty)*) => ($(
+      impl FromSize for $t {
+          fn from_size(whole: u128, fraction: f64) -> Option<Self> {
+              let rounded = if fraction >= 0.5 { whole.checked_add(1)? } else { whole };
+              <$t>::try_from(rounded).ok()
+          }
+      }
+  )*)
+}
+
+impl_from_size_int!(for usize u8 u16 u32 u64 u128 isize i8 i16 i32 i64 i128);
+
+macro_rules! impl_from_size_float {
+  (for $($t:ty)*) => ($(
+      impl FromSize for $t {
+          fn from_size(whole: u128, fraction: f64) -> Option<Self> {
+              Some((whole as f64 + fraction) as $t)
+          }
+      }
+  )*)
+}
+
+impl_from_size_float!(for f32 f64);
@@ -0,0 +1,77 @@
+use super::{
+    BaseUnit, FixedAt, FormatSizeOptions, Kilo, OptionsError, Overflow, Rounding, ScaleStep,
+};
+use crate::UnitTable;
+
+/// Builds a `FormatSizeOptions`, checking that the options can be used together once built.
+/// Its setters mirror those of `FormatSizeOptions`, which is suited to options known at compile time.
+///
+/// ```rust
+/// use humansize::{format_size, FormatSizeOptionsBuilder, OptionsError, BINARY};
+///
+/// let options = FormatSizeOptionsBuilder::new(BINARY).decimal_places(1).build().unwrap();
+/// assert_eq!(format_size(1536u32, options), "1.5 KiB");
+///
+/// let invalid = FormatSizeOptionsBuilder::new(BINARY).thousands_separator(Some('.')).build();
+/// assert_eq!(invalid.unwrap_err(), OptionsError::AmbiguousSeparator);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatSizeOptionsBuilder {
+    options: FormatSizeOptions,
+}
+
+macro_rules! setters {
+    ($($name:ident: $type:ty,)*) => ($(
+        pub const fn $name(mut self, $name: $type) -> FormatSizeOptionsBuilder {
+            self.options.$name = $name;
+            self
+        }
+    )*)
+}
+
+impl FormatSizeOptionsBuilder {
+    /// Starts from a set of options, usually one of the presets.
+    pub const fn new(base: FormatSizeOptions) -> FormatSizeOptionsBuilder {
+        FormatSizeOptionsBuilder { options: base }
+    }
+
+    setters! {
+        base_unit: BaseUnit,
+        kilo: Kilo,
+        units: Kilo,
+        decimal_places: usize,
+        decimal_zeroes: usize,
+        fixed_at: Option<FixedAt>,
+        long_units: bool,
+        space_after_value: bool,
+        suffix: &'static str,
+        thousands_separator: Option<char>,
+        significant_digits: Option<usize>,
+        rounding: Rounding,
+        rollover_threshold: Option<u32>,
+        placeholder: Option<&'static str>,
+        extended_prefixes: bool,
+        overflow: Overflow,
+        scale_ladder: Option<&'static [ScaleStep]>,
+        unit_table: Option<UnitTable>,
+        min_unit: Option<FixedAt>,
+        max_unit: Option<FixedAt>,
+        unit_decimal_places: Option<&'static [usize]>,
+        min_fraction_digits: Option<usize>,
+        max_fraction_digits: Option<usize>,
+    }
+
+    /// Returns the options, or the reason why they can't be used together.
+    pub const fn build(self) -> Result<FormatSizeOptions, OptionsError> {
+        match self.options.validate() {
+            Ok(()) => Ok(self.options),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl From<FormatSizeOptions> for FormatSizeOptionsBuilder {
+    fn from(options: FormatSizeOptions) -> Self {
+        FormatSizeOptionsBuilder::new(options)
+    }
+}
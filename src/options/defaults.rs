@@ -1,4 +1,4 @@
-use super::{BaseUnit, FormatSizeOptions, Kilo};
+use super::{BaseUnit, FormatSizeOptions, Kilo, Overflow, Rounding};
 
 /// Options to display sizes in the SI format.
 pub const BINARY: FormatSizeOptions = FormatSizeOptions {
@@ -12,6 +12,19 @@ pub const BINARY: FormatSizeOptions = FormatSizeOptions {
     space_after_value: true,
     suffix: "",
     thousands_separator: None,
+    significant_digits: None,
+    rounding: Rounding::HalfEven,
+    rollover_threshold: None,
+    placeholder: None,
+    extended_prefixes: false,
+    overflow: Overflow::Grow,
+    scale_ladder: None,
+    unit_table: None,
+    min_unit: None,
+    max_unit: None,
+    unit_decimal_places: None,
+    min_fraction_digits: None,
+    max_fraction_digits: None,
 };
 
 /// Options to display sizes in the SI (decimal) format.
@@ -26,6 +39,19 @@ pub const DECIMAL: FormatSizeOptions = FormatSizeOptions {
     space_after_value: true,
     suffix: "",
     thousands_separator: None,
+    significant_digits: None,
+    rounding: Rounding::HalfEven,
+    rollover_threshold: None,
+    placeholder: None,
+    extended_prefixes: false,
+    overflow: Overflow::Grow,
+    scale_ladder: None,
+    unit_table: None,
+    min_unit: None,
+    max_unit: None,
+    unit_decimal_places: None,
+    min_fraction_digits: None,
+    max_fraction_digits: None,
 };
 
 /// Options to display sizes in the "WINDOWS" format.
@@ -41,4 +67,45 @@ pub const WINDOWS: FormatSizeOptions = FormatSizeOptions {
     space_after_value: true,
     suffix: "",
     thousands_separator: None,
+    significant_digits: None,
+    rounding: Rounding::HalfEven,
+    rollover_threshold: None,
+    placeholder: None,
+    extended_prefixes: false,
+    overflow: Overflow::Grow,
+    scale_ladder: None,
+    unit_table: None,
+    min_unit: None,
+    max_unit: None,
+    unit_decimal_places: None,
+    min_fraction_digits: None,
+    max_fraction_digits: None,
+};
+
+/// Options to display sizes in the JEDEC format, as used for memory modules.
+/// Uses 1024 as the value of the `Kilo`, and JEDEC units (`KB`, not `kB` or `KiB`).
+pub const JEDEC: FormatSizeOptions = FormatSizeOptions {
+    base_unit: BaseUnit::Byte,
+    kilo: Kilo::Jedec,
+    units: Kilo::Jedec,
+    decimal_places: 2,
+    decimal_zeroes: 0,
+    fixed_at: None,
+    long_units: false,
+    space_after_value: true,
+    suffix: "",
+    thousands_separator: None,
+    significant_digits: None,
+    rounding: Rounding::HalfEven,
+    rollover_threshold: None,
+    placeholder: None,
+    extended_prefixes: false,
+    overflow: Overflow::Grow,
+    scale_ladder: None,
+    unit_table: None,
+    min_unit: None,
+    max_unit: None,
+    unit_decimal_places: None,
+    min_fraction_digits: None,
+    max_fraction_digits: None,
 };
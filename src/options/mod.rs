@@ -1,16 +1,35 @@
 //! Describes the struct that holds the options needed by the formatting functions.
 //! The three most common formats are provided as constants to be used easily
 
+use core::fmt;
+
+use libm::{ceil, fabs, floor, pow, round, trunc};
+
+use crate::{ISizeFormatter, ToF64, UnitTable};
+
+mod builder;
 mod defaults;
+pub use self::builder::FormatSizeOptionsBuilder;
 pub use self::defaults::*;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 /// Holds the standard to use when displaying the size.
+#[non_exhaustive]
 pub enum Kilo {
     /// The decimal scale and units. SI standard.
     Decimal,
     /// The binary scale and units.
     Binary,
+    /// The binary scale with the units of the JEDEC standard (`KB`, `MB`), as used for memory modules
+    /// and by Windows Explorer.
+    Jedec,
+    /// A scale of any base (e.g. 512 for blocks). As a set of units, it displays the decimal ones.
+    Custom(u32),
 }
 
 impl Default for Kilo {
@@ -23,13 +42,20 @@ impl Kilo {
     pub(crate) fn value(&self) -> f64 {
         match self {
             Kilo::Decimal => 1000.0,
-            Kilo::Binary => 1024.0,
+            Kilo::Binary | Kilo::Jedec => 1024.0,
+            Kilo::Custom(n) => *n as f64,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 /// Forces a certain representation of the resulting file size.
+#[non_exhaustive]
 pub enum FixedAt {
     Base,
     Kilo,
@@ -40,9 +66,44 @@ pub enum FixedAt {
     Exa,
     Zetta,
     Yotta,
+    Ronna,
+    Quetta,
 }
 
+impl FixedAt {
+    /// Every scale, from the base unit up.
+    pub(crate) const ALL: [FixedAt; 11] = [
+        FixedAt::Base,
+        FixedAt::Kilo,
+        FixedAt::Mega,
+        FixedAt::Giga,
+        FixedAt::Tera,
+        FixedAt::Peta,
+        FixedAt::Exa,
+        FixedAt::Zetta,
+        FixedAt::Yotta,
+        FixedAt::Ronna,
+        FixedAt::Quetta,
+    ];
+}
+
+/// The unit a size would be displayed in, as returned by `FormatSizeOptions::scale_for`.
 #[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SizeScale {
+    /// The index of the unit, as in `FixedAt` (e.g. `1` for kilo) or in the scale ladder.
+    pub scale: usize,
+    /// The unit at that index, or `None` for the steps of a scale ladder beyond `Quetta`.
+    pub fixed_at: Option<FixedAt>,
+    /// The value in that unit, before rounding.
+    pub value: f64,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum BaseUnit {
     Bit,
     Byte,
@@ -54,8 +115,110 @@ impl Default for BaseUnit {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+/// How the displayed value is rounded to the amount of decimal places.
+pub enum Rounding {
+    /// Rounds to the nearest value, halfway cases away from zero.
+    HalfUp,
+    /// Rounds to the nearest value, halfway cases to the nearest even digit, as `format!` does. The default.
+    HalfEven,
+    /// Rounds towards negative infinity, so that e.g. free space is never overstated.
+    Floor,
+    /// Rounds towards positive infinity, so that e.g. used space is never understated.
+    Ceil,
+    /// Rounds towards zero.
+    Truncate,
+}
+
+impl Default for Rounding {
+    fn default() -> Self {
+        Self::HalfEven
+    }
+}
+
+impl Rounding {
+    pub(crate) fn round(&self, value: f64) -> f64 {
+        match self {
+            Rounding::HalfUp => round(value),
+            Rounding::HalfEven => {
+                if fabs(value - trunc(value)) == 0.5 {
+                    2.0 * round(value / 2.0)
+                } else {
+                    round(value)
+                }
+            }
+            Rounding::Floor => floor(value),
+            Rounding::Ceil => ceil(value),
+            Rounding::Truncate => trunc(value),
+        }
+    }
+
+    /// Rounds `value` to `places` decimal places.
+    pub(crate) fn round_to(&self, value: f64, places: usize) -> f64 {
+        let factor = pow(10f64, places as f64);
+        let scaled = value * factor;
+
+        // Past 2^53, floats can't hold a fractional part: the value is already as rounded as it can be
+        if !scaled.is_finite() || fabs(scaled) >= 9_007_199_254_740_992.0 {
+            return value;
+        }
+        self.round(scaled) / factor
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+/// How values beyond the largest unit are displayed (e.g. 1234 YB without `extended_prefixes`).
+pub enum Overflow {
+    /// Displays more integer digits in the largest unit: `1234 YB`.
+    Grow,
+    /// Switches to scientific notation: `1.23e3 YB`.
+    Scientific,
+    /// Switches to engineering notation, the exponent being a multiple of three: `1.23e3 YB`, `12.35e3 YB`.
+    Engineering,
+    /// Displays the largest value below the rollover threshold, marked as a bound: `> 999 YB`.
+    Saturate,
+}
+
+impl Default for Overflow {
+    fn default() -> Self {
+        Self::Grow
+    }
+}
+
+/// A unit of a custom ladder of units (see `FormatSizeOptions::scale_ladder`). Ladders are ordered by increasing
+/// multiplier, the value switching to the next step once it reaches one of its unit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ScaleStep {
+    /// The amount of base units in one of this unit (e.g. 1_000_000 for `MB`).
+    pub multiplier: u128,
+    /// The label displayed after values in this unit, as is.
+    pub label: &'static str,
+}
+
+impl ScaleStep {
+    pub const fn new(multiplier: u128, label: &'static str) -> ScaleStep {
+        ScaleStep { multiplier, label }
+    }
+}
+
 /// Holds the options for the `file_size` method.
+///
+/// With the `serde` feature, options can be (de)serialized. Deserializing a `suffix` or a `placeholder` other than a
+/// few common ones, a `scale_ladder`, the labels of a `unit_table` or `unit_decimal_places` leaks them to get the
+/// `&'static` references the options hold, so options are meant to be deserialized once, e.g. at startup.
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 #[non_exhaustive]
 pub struct FormatSizeOptions {
     /// Whether the value being formatted represents an amount of bits or bytes.
@@ -87,6 +250,45 @@ pub struct FormatSizeOptions {
 
     // If the number is too great for the scale that was chosen, or so great that it exceeds the largest unit of the scale, the thousands separator will be used if provided. By default, it is None and no formatting will occur
     pub thousands_separator: Option<char>,
+
+    /// Displays this amount of significant digits instead of a fixed amount of decimal places, keeping trailing zeros (e.g. `1.00 KiB`, `12.3 MiB`, `123 MiB` with 3).
+    pub significant_digits: Option<usize>,
+
+    /// How to round the displayed value.
+    pub rounding: Rounding,
+
+    /// The displayed value from which to switch to the next unit, the smaller of the kilo and 1000 by default (e.g. `0.98 KiB` rather than `1000 B`).
+    pub rollover_threshold: Option<u32>,
+
+    /// A string displayed instead of the size when the value can't be displayed: not finite (`NaN`, infinities) or beyond the largest unit. With it, `try_format_size` no longer reports these values as errors, which suits dashboards (e.g. `—` or `n/a`).
+    pub placeholder: Option<&'static str>,
+
+    /// Whether to scale values up to the ronna (`RB`) and quetta (`QB`) prefixes of the 2022 SI revision. Without them, the largest automatically chosen unit is yotta (`YB`), and larger values are displayed with more integer digits in it. `fixed_at` can use them regardless.
+    pub extended_prefixes: bool,
+
+    /// What to display when the value still reaches the rollover threshold in the largest unit.
+    pub overflow: Overflow,
+
+    /// An explicit ladder of units to walk instead of the standard ones (e.g. `B`, `MB`, `GB`), `fixed_at`, `min_unit` and `max_unit` selecting a step by its position.
+    pub scale_ladder: Option<&'static [ScaleStep]>,
+
+    /// The unit labels to display instead of those selected by `units`, `long_units` and `base_unit` (see `UnitTable`).
+    pub unit_table: Option<UnitTable>,
+
+    /// The smallest unit automatic scaling may choose. Values too small to be displayed in it are shown as a bound, e.g. `< 0.01 KiB`. Ignored when `fixed_at` is set.
+    pub min_unit: Option<FixedAt>,
+
+    /// The largest unit automatic scaling may choose. Larger values are displayed with more integer digits in it. Ignored when `fixed_at` is set.
+    pub max_unit: Option<FixedAt>,
+
+    /// The amount of decimal places to display for each unit, indexed like `fixed_at` (e.g. `&[0, 0, 1, 2]` for none in bytes and kilobytes, one in megabytes, and two in larger units, the last entry applying to the following units). Replaces `decimal_places` when set.
+    pub unit_decimal_places: Option<&'static [usize]>,
+
+    /// The least amount of decimal places to display, like ICU's minimum fraction digits, `decimal_places` being the most. Trailing zeros beyond it are removed and missing ones added, e.g. `1.5 MiB` and `1.0 MiB` with one minimum and two decimal places. Replaces `decimal_zeroes` when set.
+    pub min_fraction_digits: Option<usize>,
+
+    /// The most decimal places to display, like ICU's maximum fraction digits, whichever option chooses them (e.g. `1.2 MiB` rather than `1.23 MiB` with one maximum and three significant digits).
+    pub max_fraction_digits: Option<usize>,
 }
 
 impl FormatSizeOptions {
@@ -143,6 +345,338 @@ impl FormatSizeOptions {
         self.thousands_separator = sep;
         self
     }
+
+    pub const fn significant_digits(
+        mut self,
+        significant_digits: Option<usize>,
+    ) -> FormatSizeOptions {
+        self.significant_digits = significant_digits;
+        self
+    }
+
+    pub const fn rounding(mut self, rounding: Rounding) -> FormatSizeOptions {
+        self.rounding = rounding;
+        self
+    }
+
+    pub const fn rollover_threshold(
+        mut self,
+        rollover_threshold: Option<u32>,
+    ) -> FormatSizeOptions {
+        self.rollover_threshold = rollover_threshold;
+        self
+    }
+
+    pub const fn placeholder(mut self, placeholder: Option<&'static str>) -> FormatSizeOptions {
+        self.placeholder = placeholder;
+        self
+    }
+
+    pub const fn extended_prefixes(mut self, extended_prefixes: bool) -> FormatSizeOptions {
+        self.extended_prefixes = extended_prefixes;
+        self
+    }
+
+    pub const fn overflow(mut self, overflow: Overflow) -> FormatSizeOptions {
+        self.overflow = overflow;
+        self
+    }
+
+    pub const fn scale_ladder(
+        mut self,
+        scale_ladder: Option<&'static [ScaleStep]>,
+    ) -> FormatSizeOptions {
+        self.scale_ladder = scale_ladder;
+        self
+    }
+
+    pub const fn unit_table(mut self, unit_table: Option<UnitTable>) -> FormatSizeOptions {
+        self.unit_table = unit_table;
+        self
+    }
+
+    pub const fn min_unit(mut self, min_unit: Option<FixedAt>) -> FormatSizeOptions {
+        self.min_unit = min_unit;
+        self
+    }
+
+    pub const fn max_unit(mut self, max_unit: Option<FixedAt>) -> FormatSizeOptions {
+        self.max_unit = max_unit;
+        self
+    }
+
+    pub const fn unit_decimal_places(
+        mut self,
+        unit_decimal_places: Option<&'static [usize]>,
+    ) -> FormatSizeOptions {
+        self.unit_decimal_places = unit_decimal_places;
+        self
+    }
+
+    pub const fn min_fraction_digits(
+        mut self,
+        min_fraction_digits: Option<usize>,
+    ) -> FormatSizeOptions {
+        self.min_fraction_digits = min_fraction_digits;
+        self
+    }
+
+    pub const fn max_fraction_digits(
+        mut self,
+        max_fraction_digits: Option<usize>,
+    ) -> FormatSizeOptions {
+        self.max_fraction_digits = max_fraction_digits;
+        self
+    }
+}
+
+/// The most decimal places (or significant digits) the options can ask for.
+pub(crate) const MAX_PLACES: usize = 38;
+
+impl FormatSizeOptions {
+    /// Checks that the options can be used together, so that options loaded at runtime can be rejected upfront
+    /// instead of being misrendered later.
+    pub const fn validate(&self) -> Result<(), OptionsError> {
+        if self.decimal_places > MAX_PLACES {
+            return Err(OptionsError::TooManyDecimalPlaces);
+        }
+        if let Some(places) = self.unit_decimal_places {
+            let mut i = 0;
+            while i < places.len() {
+                if places[i] > MAX_PLACES {
+                    return Err(OptionsError::TooManyDecimalPlaces);
+                }
+                i += 1;
+            }
+        }
+        if self.decimal_zeroes > MAX_PLACES {
+            return Err(OptionsError::TooManyDecimalZeroes);
+        }
+        if let Err(err) = self.validate_fraction_digits() {
+            return Err(err);
+        }
+        if let Some(sep) = self.thousands_separator {
+            if sep == '.' || sep.is_ascii_digit() {
+                return Err(OptionsError::AmbiguousSeparator);
+            }
+        }
+        if let Some(digits) = self.significant_digits {
+            if digits == 0 || digits > MAX_PLACES {
+                return Err(OptionsError::InvalidSignificantDigits);
+            }
+        }
+        if let Some(0) = self.rollover_threshold {
+            return Err(OptionsError::ZeroRolloverThreshold);
+        }
+        if let Kilo::Custom(0..=1) = self.kilo {
+            return Err(OptionsError::InvalidKilo);
+        }
+        if let (Some(min), Some(max)) = (self.min_unit, self.max_unit) {
+            if min as usize > max as usize {
+                return Err(OptionsError::InvalidUnitRange);
+            }
+        }
+        if let Err(err) = self.validate_unit_table() {
+            return Err(err);
+        }
+        self.validate_ladder()
+    }
+
+    const fn validate_fraction_digits(&self) -> Result<(), OptionsError> {
+        let max = match self.max_fraction_digits {
+            Some(max) if max > MAX_PLACES => return Err(OptionsError::TooManyFractionDigits),
+            Some(max) => max,
+            None => MAX_PLACES,
+        };
+        let min = match self.min_fraction_digits {
+            Some(min) if min > MAX_PLACES => return Err(OptionsError::TooManyFractionDigits),
+            Some(min) => min,
+            None => return Ok(()),
+        };
+        if min > max {
+            return Err(OptionsError::InvalidFractionDigits);
+        }
+        // Without significant digits, the decimal places of every unit are the most displayed
+        if self.significant_digits.is_none() {
+            match self.unit_decimal_places {
+                Some(places) if !places.is_empty() => {
+                    let mut i = 0;
+                    while i < places.len() {
+                        if min > places[i] {
+                            return Err(OptionsError::InvalidFractionDigits);
+                        }
+                        i += 1;
+                    }
+                }
+                _ => {
+                    if min > self.decimal_places {
+                        return Err(OptionsError::InvalidFractionDigits);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    const fn validate_unit_table(&self) -> Result<(), OptionsError> {
+        if let Some(table) = self.unit_table {
+            let fixed_idx = match self.fixed_at {
+                Some(fixed_at) if self.scale_ladder.is_none() => fixed_at as usize,
+                _ => 0,
+            };
+            if fixed_idx >= table.labels.len() {
+                return Err(OptionsError::InvalidUnitTable);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the unit the formatters would display the value in with these options, along with the value in
+    /// that unit before rounding. The unit is chosen exactly as when formatting, honoring `fixed_at`, `min_unit`,
+    /// `max_unit` and the rounding of the displayed value.
+    ///
+    /// With a `scale_ladder`, the unit is the position of the step, `Base` being the first.
+    ///
+    /// ```rust
+    /// use humansize::{FixedAt, SizeScale, BINARY};
+    ///
+    /// assert_eq!(
+    ///     BINARY.scale_for(1536u32),
+    ///     SizeScale { scale: 1, fixed_at: Some(FixedAt::Kilo), value: 1.5 }
+    /// );
+    /// assert_eq!(BINARY.scale_for(1_048_575u32).fixed_at, Some(FixedAt::Mega));
+    /// ```
+    pub fn scale_for(&self, value: impl ToF64) -> SizeScale {
+        let formatter = ISizeFormatter::new(value, self.displayable());
+        let options = formatter.options;
+        let (scale_idx, _) = formatter.select_scale(&options);
+        SizeScale {
+            scale: scale_idx,
+            fixed_at: FixedAt::ALL.get(scale_idx).copied(),
+            value: formatter.to_scaled_f64(scale_idx, &options),
+        }
+    }
+
+    /// Drops the options which can't be displayed at all, so that formatting invalid options never panics:
+    /// an invalid ladder or unit table is ignored, a custom kilo below 2 falls back to the decimal one, and the
+    /// amounts of decimals are limited to 38 as `validate` requires.
+    pub(crate) const fn displayable(mut self) -> FormatSizeOptions {
+        if self.validate_ladder().is_err() {
+            self.scale_ladder = None;
+        }
+        if self.validate_unit_table().is_err() {
+            self.unit_table = None;
+        }
+        if let Kilo::Custom(0..=1) = self.kilo {
+            self.kilo = Kilo::Decimal;
+        }
+        if self.decimal_places > MAX_PLACES {
+            self.decimal_places = MAX_PLACES;
+        }
+        if self.decimal_zeroes > MAX_PLACES {
+            self.decimal_zeroes = MAX_PLACES;
+        }
+        if let Some(MAX_PLACES..=usize::MAX) = self.min_fraction_digits {
+            self.min_fraction_digits = Some(MAX_PLACES);
+        }
+        if let Some(MAX_PLACES..=usize::MAX) = self.max_fraction_digits {
+            self.max_fraction_digits = Some(MAX_PLACES);
+        }
+        if let Some(0) = self.significant_digits {
+            self.significant_digits = Some(1);
+        } else if let Some(MAX_PLACES..=usize::MAX) = self.significant_digits {
+            self.significant_digits = Some(MAX_PLACES);
+        }
+        if let Some(places) = self.unit_decimal_places {
+            let mut i = 0;
+            while i < places.len() {
+                if places[i] > MAX_PLACES {
+                    self.unit_decimal_places = None;
+                }
+                i += 1;
+            }
+        }
+        self
+    }
+
+    const fn validate_ladder(&self) -> Result<(), OptionsError> {
+        if let Some(ladder) = self.scale_ladder {
+            if ladder.is_empty() || ladder[0].multiplier == 0 {
+                return Err(OptionsError::InvalidLadder);
+            }
+            let mut i = 1;
+            while i < ladder.len() {
+                if ladder[i].multiplier <= ladder[i - 1].multiplier {
+                    return Err(OptionsError::InvalidLadder);
+                }
+                i += 1;
+            }
+            if let Some(fixed_at) = self.fixed_at {
+                if fixed_at as usize >= ladder.len() {
+                    return Err(OptionsError::InvalidLadder);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The reasons why a set of options is rejected by `FormatSizeOptions::validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum OptionsError {
+    /// `decimal_places`, or one of the `unit_decimal_places`, is above 38.
+    TooManyDecimalPlaces,
+    /// `decimal_zeroes` is above 38.
+    TooManyDecimalZeroes,
+    /// `min_fraction_digits` or `max_fraction_digits` is above 38.
+    TooManyFractionDigits,
+    /// `min_fraction_digits` is above the most decimal places displayed: `max_fraction_digits`, or `decimal_places`
+    /// (one of the `unit_decimal_places`) without significant digits.
+    InvalidFractionDigits,
+    /// The thousands separator is the decimal point or a digit, so the output couldn't be read back.
+    AmbiguousSeparator,
+    /// `significant_digits` is zero or above 38.
+    InvalidSignificantDigits,
+    /// `rollover_threshold` is zero, so no unit could ever be displayed.
+    ZeroRolloverThreshold,
+    /// The custom kilo is below 2, so the units wouldn't grow.
+    InvalidKilo,
+    /// The scale ladder is empty, its multipliers are zero or not increasing, or `fixed_at` is beyond its last step.
+    InvalidLadder,
+    /// The unit table is empty, or `fixed_at` is beyond its last unit.
+    InvalidUnitTable,
+    /// `min_unit` is larger than `max_unit`.
+    InvalidUnitRange,
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            OptionsError::TooManyDecimalPlaces => "decimal_places must be at most 38",
+            OptionsError::TooManyDecimalZeroes => "decimal_zeroes must be at most 38",
+            OptionsError::TooManyFractionDigits => {
+                "min_fraction_digits and max_fraction_digits must be at most 38"
+            }
+            OptionsError::InvalidFractionDigits => {
+                "min_fraction_digits can't be larger than the most decimal places displayed"
+            }
+            OptionsError::AmbiguousSeparator => {
+                "thousands_separator can't be the decimal point or a digit"
+            }
+            OptionsError::InvalidSignificantDigits => "significant_digits must be between 1 and 38",
+            OptionsError::ZeroRolloverThreshold => "rollover_threshold can't be zero",
+            OptionsError::InvalidKilo => "a custom kilo must be at least 2",
+            OptionsError::InvalidUnitTable => {
+                "unit_table must not be empty and must contain the fixed_at unit"
+            }
+            OptionsError::InvalidUnitRange => "min_unit can't be larger than max_unit",
+            OptionsError::InvalidLadder => {
+                "scale_ladder must have increasing, non-zero multipliers and contain the fixed_at step"
+            }
+        };
+        f.write_str(msg)
+    }
 }
 
 impl AsRef<FormatSizeOptions> for FormatSizeOptions {
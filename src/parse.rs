@@ -0,0 +1,301 @@
+//! Parses human-readable sizes (e.g. `1.5 GiB`) back into amounts of bytes or bits.
+
+use core::fmt;
+
+use crate::{scales, BaseUnit, FormatSizeOptions, FromSize, Kilo};
+
+/// Holds the options for the `parse_size` function.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct ParseSizeOptions {
+    /// The value of the kilo for units that don't carry one (e.g. `kB`). Binary (e.g. `KiB`) and JEDEC-only units (e.g. `KB`)
+    /// are always 1024-based. A custom kilo below 2 falls back to the decimal one.
+    pub kilo: Kilo,
+
+    /// Whether the parsed value should be expressed in bits or bytes. Sizes written in the other unit are converted.
+    pub base_unit: BaseUnit,
+
+    /// An optional separator which may appear between groups of digits of the integer part (e.g. `1,024 KiB`).
+    pub thousands_separator: Option<char>,
+}
+
+impl ParseSizeOptions {
+    pub const fn new() -> ParseSizeOptions {
+        ParseSizeOptions {
+            kilo: Kilo::Decimal,
+            base_unit: BaseUnit::Byte,
+            thousands_separator: None,
+        }
+    }
+
+    pub const fn kilo(mut self, kilo: Kilo) -> ParseSizeOptions {
+        self.kilo = kilo;
+        self
+    }
+
+    pub const fn base_unit(mut self, base_unit: BaseUnit) -> ParseSizeOptions {
+        self.base_unit = base_unit;
+        self
+    }
+
+    pub const fn thousands_separator(mut self, sep: Option<char>) -> ParseSizeOptions {
+        self.thousands_separator = sep;
+        self
+    }
+}
+
+/// Parses sizes the same way they would be formatted with these options, so that `WINDOWS` reads `1 kB` as 1024 bytes.
+/// Options which don't display decimal units keep the standard meaning of these units.
+impl From<FormatSizeOptions> for ParseSizeOptions {
+    fn from(options: FormatSizeOptions) -> Self {
+        ParseSizeOptions {
+            kilo: match options.units {
+                Kilo::Decimal | Kilo::Jedec | Kilo::Custom(_) => options.kilo,
+                Kilo::Binary => Kilo::Decimal,
+            },
+            base_unit: options.base_unit,
+            thousands_separator: options.thousands_separator,
+        }
+    }
+}
+
+impl<'a> From<&'a FormatSizeOptions> for ParseSizeOptions {
+    fn from(options: &'a FormatSizeOptions) -> Self {
+        ParseSizeOptions::from(*options)
+    }
+}
+
+impl<'a> From<&'a ParseSizeOptions> for ParseSizeOptions {
+    fn from(options: &'a ParseSizeOptions) -> Self {
+        *options
+    }
+}
+
+/// The reasons why a string could not be parsed into a size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseSizeError {
+    /// The input is empty or only contains whitespace.
+    Empty,
+    /// The numeric part of the input is missing or malformed.
+    InvalidNumber,
+    /// The unit following the number is not one of the units the crate knows about.
+    UnknownUnit,
+    /// The size does not fit in the requested type.
+    Overflow,
+}
+
+impl fmt::Display for ParseSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            ParseSizeError::Empty => "cannot parse a size from an empty string",
+            ParseSizeError::InvalidNumber => "invalid number in size",
+            ParseSizeError::UnknownUnit => "unrecognized size unit",
+            ParseSizeError::Overflow => "size is too large for the target type",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Every label the formatter can emit, along with the standard and base unit it belongs to.
+/// JEDEC labels come last so that those shared with the SI (e.g. `MB`) keep their decimal meaning.
+const LABEL_TABLES: [(&[&str; scales::SCALE_LENGTH], Kilo, BaseUnit); 14] = [
+    (&scales::SCALE_BINARY, Kilo::Binary, BaseUnit::Byte),
+    (&scales::SCALE_BINARY_LONG, Kilo::Binary, BaseUnit::Byte),
+    (&scales::SCALE_BINARY_BIT, Kilo::Binary, BaseUnit::Bit),
+    (&scales::SCALE_BINARY_BIT_LONG, Kilo::Binary, BaseUnit::Bit),
+    (&scales::SCALE_DECIMAL, Kilo::Decimal, BaseUnit::Byte),
+    (&scales::SCALE_DECIMAL_LONG, Kilo::Decimal, BaseUnit::Byte),
+    (&scales::SCALE_DECIMAL_BIT, Kilo::Decimal, BaseUnit::Bit),
+    (
+        &scales::SCALE_DECIMAL_BIT_LONG,
+        Kilo::Decimal,
+        BaseUnit::Bit,
+    ),
+    (&scales::SCALE_JEDEC, Kilo::Jedec, BaseUnit::Byte),
+    (&scales::SCALE_JEDEC_BIT, Kilo::Jedec, BaseUnit::Bit),
+    (&scales::SCALE_JEDEC_LONG, Kilo::Jedec, BaseUnit::Byte),
+    (
+        &scales::SCALE_JEDEC_LONG_SINGULAR,
+        Kilo::Jedec,
+        BaseUnit::Byte,
+    ),
+    (&scales::SCALE_JEDEC_BIT_LONG, Kilo::Jedec, BaseUnit::Bit),
+    (
+        &scales::SCALE_JEDEC_BIT_LONG_SINGULAR,
+        Kilo::Jedec,
+        BaseUnit::Bit,
+    ),
+];
+
+fn label_matches(label: &str, candidate: &str, ignore_case: bool) -> bool {
+    // Only the prefix is folded, a final `b` standing for bits and `B` for bytes (e.g. `Mb` and `MB`)
+    let eq = |a: &str, b: &str| {
+        if ignore_case {
+            a.eq_ignore_ascii_case(b) && a.ends_with('b') == b.ends_with('b')
+        } else {
+            a == b
+        }
+    };
+
+    // Plural labels are also accepted in their singular form (e.g. `Kilobyte`, `bit`),
+    // and `bit` in its short form (e.g. `Mb` for `Mbit`)
+    let singular = candidate.strip_suffix('s').unwrap_or(candidate);
+    eq(label, candidate)
+        || eq(label, singular)
+        || match (label.strip_suffix('b'), singular.strip_suffix("bit")) {
+            (Some(prefix), Some(candidate_prefix)) => eq(prefix, candidate_prefix),
+            _ => false,
+        }
+}
+
+/// Finds the standard, base unit and scale index of a unit label.
+/// Exact matches are preferred over case-insensitive ones.
+pub(crate) fn find_unit(label: &str) -> Option<(Kilo, BaseUnit, usize)> {
+    for &ignore_case in &[false, true] {
+        for (table, kilo, base_unit) in LABEL_TABLES.iter() {
+            if let Some(idx) = table
+                .iter()
+                .position(|candidate| label_matches(label, candidate, ignore_case))
+            {
+                return Some((*kilo, *base_unit, idx));
+            }
+        }
+    }
+    None
+}
+
+/// Keeps `10^fraction_digits * 8` within a u128.
+const MAX_FRACTION_DIGITS: u32 = 37;
+
+/// Splits the input into the digits of the number and the fractional digit count, and the unit label.
+/// The separator is only accepted between groups of three digits of the integer part (e.g. `1,024` but not `1,0,0`).
+fn parse_number(input: &str, sep: Option<char>) -> Result<(u128, u32, &str), ParseSizeError> {
+    let mut mantissa: u128 = 0;
+    let mut fraction_digits: u32 = 0;
+    let mut seen_digit = false;
+    let mut seen_point = false;
+    let mut seen_sep = false;
+    // Digits of the integer part since the last separator
+    let mut group_len = 0;
+    let mut rest = "";
+
+    let input = input.strip_prefix('+').unwrap_or(input);
+
+    for (pos, c) in input.char_indices() {
+        if let Some(digit) = c.to_digit(10) {
+            seen_digit = true;
+            if !seen_point {
+                group_len += 1;
+                mantissa = mantissa
+                    .checked_mul(10)
+                    .and_then(|m| m.checked_add(digit as u128))
+                    .ok_or(ParseSizeError::Overflow)?;
+            } else if fraction_digits < MAX_FRACTION_DIGITS {
+                // Digits beyond what a u128 can hold are dropped, they are below any meaningful precision
+                if let Some(m) = mantissa
+                    .checked_mul(10)
+                    .and_then(|m| m.checked_add(digit as u128))
+                {
+                    mantissa = m;
+                    fraction_digits += 1;
+                }
+            }
+        } else if c == '.' && !seen_point {
+            seen_point = true;
+            if seen_sep && group_len != 3 {
+                return Err(ParseSizeError::InvalidNumber);
+            }
+        } else if Some(c) == sep && !seen_point && seen_digit {
+            // The first group has one to three digits, the following ones exactly three
+            if group_len > 3 || (seen_sep && group_len != 3) {
+                return Err(ParseSizeError::InvalidNumber);
+            }
+            seen_sep = true;
+            group_len = 0;
+        } else {
+            rest = &input[pos..];
+            break;
+        }
+    }
+
+    if !seen_digit || (seen_sep && !seen_point && group_len != 3) {
+        return Err(ParseSizeError::InvalidNumber);
+    }
+    // A second decimal point or a misplaced separator is part of the number, not of the unit
+    if rest.starts_with('.') || sep.map_or(false, |sep| rest.starts_with(sep)) {
+        return Err(ParseSizeError::InvalidNumber);
+    }
+
+    Ok((mantissa, fraction_digits, rest.trim()))
+}
+
+/// Parses a human-readable size into an amount of bytes (or bits, depending on the options).
+///
+/// Every unit the formatter can produce is recognized, in its short, long, singular or plural form,
+/// and regardless of the case of its prefix. A final `b` stands for bits and `B` for bytes (e.g. `Mb` and `MB`). A number without a unit is read as an amount of the options' base unit.
+///
+/// ```rust
+/// use humansize::{parse_size, ParseSizeOptions, WINDOWS};
+///
+/// assert_eq!(parse_size::<u64>("1.5 KiB", ParseSizeOptions::new()), Ok(1536));
+/// assert_eq!(parse_size::<u64>("1 kB", WINDOWS), Ok(1024));
+/// assert_eq!(parse_size::<f64>("3 Megabytes", ParseSizeOptions::new()), Ok(3_000_000.0));
+/// ```
+pub fn parse_size<T: FromSize>(
+    input: &str,
+    options: impl Into<ParseSizeOptions>,
+) -> Result<T, ParseSizeError> {
+    let options = options.into();
+    let input = input.trim();
+
+    if input.is_empty() {
+        return Err(ParseSizeError::Empty);
+    }
+
+    let (mut mantissa, mut fraction_digits, label) =
+        parse_number(input, options.thousands_separator)?;
+
+    let (kilo, base_unit, scale_idx) = if label.is_empty() {
+        (options.kilo, options.base_unit, 0)
+    } else {
+        find_unit(label).ok_or(ParseSizeError::UnknownUnit)?
+    };
+
+    let kilo = match (kilo, options.kilo) {
+        (Kilo::Binary | Kilo::Jedec, _) => 1024u128,
+        // Like in `FormatSizeOptions::displayable`, a custom kilo below 2 falls back to the decimal one
+        (_, Kilo::Custom(0..=1)) => 1000,
+        _ => options.kilo.value() as u128,
+    };
+
+    let (bit_num, bit_den) = match (base_unit, options.base_unit) {
+        (BaseUnit::Bit, BaseUnit::Byte) => (1, 8),
+        (BaseUnit::Byte, BaseUnit::Bit) => (8, 1),
+        _ => (1, 1),
+    };
+
+    let multiplier = kilo
+        .checked_pow(scale_idx as u32)
+        .and_then(|multiplier| multiplier.checked_mul(bit_num))
+        .ok_or(ParseSizeError::Overflow)?;
+
+    // Drop the least significant fractional digits until the product fits
+    let numerator = loop {
+        match mantissa.checked_mul(multiplier) {
+            Some(n) => break n,
+            None if fraction_digits > 0 => {
+                mantissa /= 10;
+                fraction_digits -= 1;
+            }
+            None => return Err(ParseSizeError::Overflow),
+        }
+    };
+    let denominator = 10u128.pow(fraction_digits) * bit_den;
+
+    T::from_size(
+        numerator / denominator,
+        (numerator % denominator) as f64 / denominator as f64,
+    )
+    .ok_or(ParseSizeError::Overflow)
+}
@@ -0,0 +1,348 @@
+//! Describes `FormattedSize`, the parts of a formatted size, from which formatters display it.
+
+use core::fmt::{self, Write};
+
+use libm::fabs;
+
+use crate::formatters::{count_digits, write_padded, write_separated};
+use crate::utils::CharCounter;
+
+/// How a displayed bound compares to the actual value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Bound {
+    /// The value is less than the displayed number (e.g. `< 0.01 KiB`).
+    LessThan,
+    /// The value is greater than the displayed number (e.g. `> 999 YiB`).
+    GreaterThan,
+}
+
+/// A value which isn't a number, displayed as `NaN` or `inf`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum NonFinite {
+    NaN,
+    /// An infinity, negative when `FormattedSize::negative` is set.
+    Infinity,
+}
+
+/// A size formatted into its parts, so that the number and the unit can be styled separately.
+/// `Display` joins them back into the text formatters display.
+///
+/// ```rust
+/// use humansize::{SizeFormatter, BINARY};
+///
+/// let parts = SizeFormatter::new(1_610_612_736u64, BINARY.thousands_separator(Some(','))).parts();
+/// assert_eq!(parts.integer().to_string(), "1");
+/// assert_eq!(parts.fraction().to_string(), "50");
+/// assert_eq!(parts.unit, "GiB");
+/// assert_eq!(parts.scale, 3);
+/// assert_eq!(parts.to_string(), "1.50 GiB");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FormattedSize {
+    /// The text displayed instead of the size when it can't be formatted. The other parts are then empty.
+    pub placeholder: Option<&'static str>,
+    /// Set when the number is a bound rather than the value (see `min_unit` and `Overflow::Saturate`).
+    pub bound: Option<Bound>,
+    /// Whether the number has a minus sign.
+    pub negative: bool,
+    /// Set instead of the digits when the value isn't a number or is infinite.
+    pub non_finite: Option<NonFinite>,
+    /// The thousands separator inserted in the integer digits, at `separator_positions`.
+    pub separator: Option<char>,
+    /// The power of ten of the number in scientific or engineering notation (e.g. `3` for `1.23e3 YB`).
+    pub exponent: Option<i32>,
+    /// Whether a space separates the number from the unit.
+    pub space: bool,
+    /// The unit label.
+    pub unit: &'static str,
+    /// The suffix displayed after the unit.
+    pub suffix: &'static str,
+    /// The index of the unit, as in `FixedAt` (e.g. `1` for kilo) or in the scale ladder.
+    pub scale: usize,
+    /// The value in that unit, before rounding.
+    pub value: f64,
+    number: Option<Number>,
+}
+
+/// The magnitude of a displayed number, written to the output only when it is displayed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Number {
+    /// An integer and the `places` decimal digits of its fraction (e.g. 1 and 50 for `1.50`).
+    Exact {
+        integer: u128,
+        fraction: u128,
+        places: usize,
+    },
+    /// A finite float, displayed with `places` decimal places.
+    Float { value: f64, places: usize },
+}
+
+impl Number {
+    fn places(&self) -> usize {
+        match *self {
+            Number::Exact { places, .. } | Number::Float { places, .. } => places,
+        }
+    }
+
+    fn write_to<W: Write + ?Sized>(&self, f: &mut W, sep: Option<char>) -> fmt::Result {
+        match *self {
+            Number::Exact {
+                integer,
+                fraction,
+                places,
+            } => {
+                write_separated(f, sep, format_args!("{}", integer))?;
+                if places > 0 {
+                    write!(f, ".{:0width$}", fraction, width = places)?;
+                }
+                Ok(())
+            }
+            Number::Float { value, places } => {
+                write_separated(f, sep, format_args!("{:.*}", places, value))
+            }
+        }
+    }
+}
+
+impl FormattedSize {
+    pub(crate) fn new(
+        unit: &'static str,
+        suffix: &'static str,
+        scale: usize,
+        value: f64,
+    ) -> FormattedSize {
+        FormattedSize {
+            placeholder: None,
+            bound: None,
+            negative: false,
+            non_finite: None,
+            separator: None,
+            exponent: None,
+            space: false,
+            unit,
+            suffix,
+            scale,
+            value,
+            number: None,
+        }
+    }
+
+    /// The digits of the integer part, without separators. Empty for placeholders and non-finite values.
+    pub fn integer(&self) -> Digits {
+        Digits {
+            number: self.number,
+            fraction: false,
+        }
+    }
+
+    /// The digits after the decimal point, empty if there are none.
+    pub fn fraction(&self) -> Digits {
+        Digits {
+            number: self.number,
+            fraction: true,
+        }
+    }
+
+    /// The positions in `integer()` before which the thousands separator is inserted.
+    pub fn separator_positions(&self) -> impl Iterator<Item = usize> {
+        let len = match self.separator {
+            Some(_) => self.integer().len(),
+            None => 0,
+        };
+        (1..len).filter(move |pos| (len - pos) % 3 == 0)
+    }
+
+    /// Sets the number to an integer and the `places` decimal digits of its fraction.
+    pub(crate) fn set_exact(
+        &mut self,
+        negative: bool,
+        integer: u128,
+        fraction: u128,
+        places: usize,
+    ) {
+        self.negative = negative;
+        self.number = Some(Number::Exact {
+            integer,
+            fraction,
+            places,
+        });
+    }
+
+    /// Sets the number to a rounded float, displayed with `places` decimal places.
+    pub(crate) fn set_float(&mut self, value: f64, places: usize) {
+        self.negative = value.is_sign_negative() && !value.is_nan();
+        if value.is_nan() {
+            self.non_finite = Some(NonFinite::NaN);
+        } else if value.is_infinite() {
+            self.non_finite = Some(NonFinite::Infinity);
+        } else {
+            self.number = Some(Number::Float {
+                value: fabs(value),
+                places,
+            });
+        }
+    }
+
+    /// Removes the trailing zeros of the fraction beyond `min` digits, and adds the missing ones.
+    pub(crate) fn set_min_fraction_digits(&mut self, min: usize) {
+        self.number = match self.number {
+            Some(Number::Exact {
+                integer,
+                mut fraction,
+                mut places,
+            }) => {
+                while places > min && fraction % 10 == 0 {
+                    fraction /= 10;
+                    places -= 1;
+                }
+                while places < min {
+                    fraction *= 10;
+                    places += 1;
+                }
+                Some(Number::Exact {
+                    integer,
+                    fraction,
+                    places,
+                })
+            }
+            Some(Number::Float { value, places }) => {
+                let mut zeros = TrailingZeros::default();
+                let _ = write!(zeros, "{:.*}", places, value);
+                Some(Number::Float {
+                    value,
+                    places: min.max(places - zeros.count),
+                })
+            }
+            None => None,
+        };
+    }
+
+    pub(crate) fn write_to<W: Write + ?Sized>(&self, f: &mut W) -> fmt::Result {
+        if let Some(placeholder) = self.placeholder {
+            return f.write_str(placeholder);
+        }
+
+        match self.bound {
+            Some(Bound::LessThan) => f.write_str("< ")?,
+            Some(Bound::GreaterThan) => f.write_str("> ")?,
+            None => {}
+        }
+        if self.negative {
+            f.write_char('-')?;
+        }
+        match (self.non_finite, self.number) {
+            (Some(NonFinite::NaN), _) => f.write_str("NaN")?,
+            (Some(NonFinite::Infinity), _) => f.write_str("inf")?,
+            (None, Some(number)) => number.write_to(f, self.separator)?,
+            (None, None) => {}
+        }
+        if let Some(exponent) = self.exponent {
+            write!(f, "e{}", exponent)?;
+        }
+        let space = if self.space { " " } else { "" };
+        write!(f, "{}{}{}", space, self.unit, self.suffix)
+    }
+}
+
+/// Honors the width, fill and alignment flags of the formatter.
+impl fmt::Display for FormattedSize {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_padded(f, |w| self.write_to(w))
+    }
+}
+
+/// The integer or fractional digits of a `FormattedSize`, displayed without separators.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Digits {
+    number: Option<Number>,
+    fraction: bool,
+}
+
+impl Digits {
+    /// The amount of digits.
+    pub fn len(&self) -> usize {
+        match (self.number, self.fraction) {
+            (Some(Number::Exact { integer, .. }), false) => count_digits(integer) as usize,
+            (Some(number), true) => number.places(),
+            _ => {
+                let mut counter = CharCounter(0);
+                let _ = write!(counter, "{}", self);
+                counter.0
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for Digits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.number {
+            Some(Number::Exact { integer, .. }) if !self.fraction => write!(f, "{}", integer),
+            Some(Number::Exact {
+                fraction, places, ..
+            }) if places > 0 => write!(f, "{:0width$}", fraction, width = places),
+            Some(Number::Float { value, places }) => {
+                let mut part = DigitsPart {
+                    inner: f,
+                    fraction: self.fraction,
+                    in_fraction: false,
+                };
+                write!(part, "{:.*}", places, value)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Forwards either the integer or the fractional digits of a formatted float.
+struct DigitsPart<'a, W: Write + ?Sized> {
+    inner: &'a mut W,
+    fraction: bool,
+    in_fraction: bool,
+}
+
+impl<'a, W: Write + ?Sized> Write for DigitsPart<'a, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if c == '.' {
+                self.in_fraction = true;
+            } else if self.in_fraction == self.fraction {
+                self.inner.write_char(c)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Counts the trailing zeros of the fraction of a formatted float.
+#[derive(Default)]
+struct TrailingZeros {
+    in_fraction: bool,
+    count: usize,
+}
+
+impl Write for TrailingZeros {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '.' => self.in_fraction = true,
+                '0' if self.in_fraction => self.count += 1,
+                _ => self.count = 0,
+            }
+        }
+        Ok(())
+    }
+}
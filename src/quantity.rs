@@ -0,0 +1,111 @@
+//! Describes `QuantityFormatter`, which formats amounts of any unit with SI prefixes.
+
+use core::fmt::{self, Write};
+
+use libm::{fabs, pow};
+
+use crate::formatters::{
+    display_error, min_places, options_with_flags, round_for_display, write_padded,
+};
+use crate::{
+    scales, FormatSizeError, FormatSizeOptions, FormattedSize, ISizeFormatter, Kilo, ToF64,
+};
+
+/// Formats an amount of any unit (e.g. `Hz`, `W`, `requests`) with SI prefixes: `k` to `Y` (or `Q` with
+/// `extended_prefixes`) for large values, and `m`, `µ`, `n` and `p` for values below 1.
+///
+/// The decimals, rounding, spacing, separator, suffix, rollover, overflow and placeholder options apply as they
+/// do to sizes, and `fixed_at`, `min_unit` and `max_unit` select among the large prefixes. The kilo is always
+/// 1000, and the options choosing the unit labels (`units`, `long_units`, `base_unit`, `unit_table`) are ignored.
+///
+/// ```rust
+/// use humansize::{QuantityFormatter, DECIMAL};
+///
+/// assert_eq!(QuantityFormatter::new(2_400_000_000u64, "Hz", DECIMAL).to_string(), "2.40 GHz");
+/// assert_eq!(QuantityFormatter::new(0.0015, "W", DECIMAL).to_string(), "1.50 mW");
+/// ```
+pub struct QuantityFormatter<'a, T: ToF64, O: AsRef<FormatSizeOptions>> {
+    inner: ISizeFormatter<T, O>,
+    unit: &'a str,
+}
+
+impl<'a, V: ToF64, O: AsRef<FormatSizeOptions>> QuantityFormatter<'a, V, O> {
+    pub fn new(value: V, unit: &'a str, options: O) -> Self {
+        QuantityFormatter {
+            inner: ISizeFormatter::new(value, options),
+            unit,
+        }
+    }
+
+    /// Creates a formatter, or reports why the value can't be displayed with these options.
+    /// Values that can't be displayed are accepted when the options set a `placeholder`, which is then displayed instead.
+    pub fn try_new(value: V, unit: &'a str, options: O) -> Result<Self, FormatSizeError> {
+        let formatter = QuantityFormatter::new(value, unit, options);
+        formatter
+            .inner
+            .check(&quantity_options(formatter.inner.options.as_ref()))?;
+        Ok(formatter)
+    }
+
+    fn write_to<W: Write + ?Sized>(&self, f: &mut W, opts: &FormatSizeOptions) -> fmt::Result {
+        let space = if opts.space_after_value { " " } else { "" };
+
+        // Values which wouldn't display any integer digit use the sub-unit prefixes, down to pico
+        let size = self.inner.value.to_f64();
+        if opts.fixed_at.is_none()
+            && opts.min_unit.is_none()
+            && opts.scale_ladder.is_none()
+            && size != 0.0
+            && fabs(round_for_display(size, true, opts).0) < 1.0
+        {
+            let mut scale_idx = 1;
+            let mut scaled = round_for_display(size * 1000.0, false, opts);
+            while scale_idx < scales::SCALE_SI_SUB_PREFIXES.len() - 1 && fabs(scaled.0) < 1.0 {
+                scale_idx += 1;
+                scaled = round_for_display(size * pow(1000.0, scale_idx as f64), false, opts);
+            }
+
+            let mut number = FormattedSize::new("", "", scale_idx, size);
+            number.separator = opts.thousands_separator;
+            number.set_float(scaled.0, scaled.1);
+            if let Some(min) = min_places(opts.decimal_places, opts) {
+                number.set_min_fraction_digits(min);
+            }
+            number.write_to(f)?;
+            let prefix = scales::SCALE_SI_SUB_PREFIXES[scale_idx];
+            return write!(f, "{}{}{}{}", space, prefix, self.unit, opts.suffix);
+        }
+
+        let (scale_idx, (scaled, places)) = self.inner.select_scale(opts);
+        if let Some(placeholder) = opts.placeholder {
+            if display_error(scale_idx, &scaled, opts).is_some() {
+                return f.write_str(placeholder);
+            }
+        }
+
+        let mut number = FormattedSize::new("", "", scale_idx, self.inner.value.to_f64());
+        self.inner
+            .set_number(&mut number, scale_idx, scaled, places, opts);
+        number.write_to(f)?;
+        let prefix = match opts.scale_ladder {
+            Some(ladder) => ladder[scale_idx].label,
+            None => scales::SCALE_SI_PREFIXES[scale_idx],
+        };
+        write!(f, "{}{}{}{}", space, prefix, self.unit, opts.suffix)
+    }
+}
+
+/// Quantities are always scaled with SI prefixes.
+fn quantity_options(options: &FormatSizeOptions) -> FormatSizeOptions {
+    FormatSizeOptions::from(*options)
+        .kilo(Kilo::Decimal)
+        .unit_table(None)
+}
+
+/// Honors the width, fill, alignment and precision flags like `ISizeFormatter`.
+impl<'a, T: ToF64, O: AsRef<FormatSizeOptions>> fmt::Display for QuantityFormatter<'a, T, O> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let opts = quantity_options(&options_with_flags(f, self.inner.options.as_ref()));
+        write_padded(f, |w| self.write_to(w, &opts))
+    }
+}
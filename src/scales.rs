@@ -1,7 +1,13 @@
-pub(crate) const SCALE_LENGTH: usize = 9;
+//! Describes the unit labels the formatters display.
 
-pub(crate) const SCALE_DECIMAL: [&str; SCALE_LENGTH] =
-    ["B", "kB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB"];
+use crate::utils::f64_eq;
+use crate::{BaseUnit, Kilo};
+
+pub(crate) const SCALE_LENGTH: usize = 11;
+
+pub(crate) const SCALE_DECIMAL: [&str; SCALE_LENGTH] = [
+    "B", "kB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB", "RB", "QB",
+];
 
 pub(crate) const SCALE_DECIMAL_LONG: [&str; SCALE_LENGTH] = [
     "Bytes",
@@ -13,10 +19,13 @@ pub(crate) const SCALE_DECIMAL_LONG: [&str; SCALE_LENGTH] = [
     "Exabytes",
     "Zettabytes",
     "Yottabytes",
+    "Ronnabytes",
+    "Quettabytes",
 ];
 
-pub(crate) const SCALE_BINARY: [&str; SCALE_LENGTH] =
-    ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB"];
+pub(crate) const SCALE_BINARY: [&str; SCALE_LENGTH] = [
+    "B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB", "RiB", "QiB",
+];
 
 pub(crate) const SCALE_BINARY_LONG: [&str; SCALE_LENGTH] = [
     "Bytes",
@@ -28,10 +37,12 @@ pub(crate) const SCALE_BINARY_LONG: [&str; SCALE_LENGTH] = [
     "Exbibytes",
     "Zebibytes",
     "Yobibytes",
+    "Robibytes",
+    "Quebibytes",
 ];
 
 pub(crate) const SCALE_DECIMAL_BIT: [&str; SCALE_LENGTH] = [
-    "bits", "kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Zbit", "Ybit",
+    "bits", "kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Zbit", "Ybit", "Rbit", "Qbit",
 ];
 
 pub(crate) const SCALE_DECIMAL_BIT_LONG: [&str; SCALE_LENGTH] = [
@@ -44,13 +55,183 @@ pub(crate) const SCALE_DECIMAL_BIT_LONG: [&str; SCALE_LENGTH] = [
     "Exabits",
     "Zettabits",
     "Yottabits",
+    "Ronnabits",
+    "Quettabits",
 ];
 
 pub(crate) const SCALE_BINARY_BIT: [&str; SCALE_LENGTH] = [
-    "bits", "Kibit", "Mibit", "Gibit", "Tibit", "Pibit", "Eibit", "Zibit", "Yibit",
+    "bits", "Kibit", "Mibit", "Gibit", "Tibit", "Pibit", "Eibit", "Zibit", "Yibit", "Ribit",
+    "Qibit",
 ];
 
 pub(crate) const SCALE_BINARY_BIT_LONG: [&str; SCALE_LENGTH] = [
-    "bits", "Kibibits", "Mebibits", "Gibibits", "Tebibits", "Pebibits", "Exbibits", "Zebibits",
+    "bits",
+    "Kibibits",
+    "Mebibits",
+    "Gibibits",
+    "Tebibits",
+    "Pebibits",
+    "Exbibits",
+    "Zebibits",
     "Yobibits",
+    "Robibits",
+    "Quebibits",
+];
+
+pub(crate) const SCALE_JEDEC: [&str; SCALE_LENGTH] = [
+    "B", "KB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB", "RB", "QB",
+];
+
+pub(crate) const SCALE_JEDEC_BIT: [&str; SCALE_LENGTH] = [
+    "bits", "Kbit", "Mbit", "Gbit", "Tbit", "Pbit", "Ebit", "Zbit", "Ybit", "Rbit", "Qbit",
+];
+
+pub(crate) const SCALE_JEDEC_LONG: [&str; SCALE_LENGTH] = [
+    "Bytes",
+    "Kilobytes (1024)",
+    "Megabytes (1024²)",
+    "Gigabytes (1024³)",
+    "Terabytes (1024⁴)",
+    "Petabytes (1024⁵)",
+    "Exabytes (1024⁶)",
+    "Zettabytes (1024⁷)",
+    "Yottabytes (1024⁸)",
+    "Ronnabytes (1024⁹)",
+    "Quettabytes (1024¹⁰)",
+];
+
+/// The singular forms of `SCALE_JEDEC_LONG`, whose plural `s` isn't the last character.
+pub(crate) const SCALE_JEDEC_LONG_SINGULAR: [&str; SCALE_LENGTH] = [
+    "Byte",
+    "Kilobyte (1024)",
+    "Megabyte (1024²)",
+    "Gigabyte (1024³)",
+    "Terabyte (1024⁴)",
+    "Petabyte (1024⁵)",
+    "Exabyte (1024⁶)",
+    "Zettabyte (1024⁷)",
+    "Yottabyte (1024⁸)",
+    "Ronnabyte (1024⁹)",
+    "Quettabyte (1024¹⁰)",
 ];
+
+pub(crate) const SCALE_JEDEC_BIT_LONG: [&str; SCALE_LENGTH] = [
+    "Bits",
+    "Kilobits (1024)",
+    "Megabits (1024²)",
+    "Gigabits (1024³)",
+    "Terabits (1024⁴)",
+    "Petabits (1024⁵)",
+    "Exabits (1024⁶)",
+    "Zettabits (1024⁷)",
+    "Yottabits (1024⁸)",
+    "Ronnabits (1024⁹)",
+    "Quettabits (1024¹⁰)",
+];
+
+pub(crate) const SCALE_JEDEC_BIT_LONG_SINGULAR: [&str; SCALE_LENGTH] = [
+    "Bit",
+    "Kilobit (1024)",
+    "Megabit (1024²)",
+    "Gigabit (1024³)",
+    "Terabit (1024⁴)",
+    "Petabit (1024⁵)",
+    "Exabit (1024⁶)",
+    "Zettabit (1024⁷)",
+    "Yottabit (1024⁸)",
+    "Ronnabit (1024⁹)",
+    "Quettabit (1024¹⁰)",
+];
+
+pub(crate) const SCALE_COMPACT: [&str; SCALE_LENGTH] =
+    ["", "K", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+
+pub(crate) const SCALE_SI_PREFIXES: [&str; SCALE_LENGTH] =
+    ["", "k", "M", "G", "T", "P", "E", "Z", "Y", "R", "Q"];
+
+pub(crate) const SCALE_SI_SUB_PREFIXES: [&str; 5] = ["", "m", "µ", "n", "p"];
+
+/// A set of unit labels, from the base unit up (e.g. `B`, `kB`, `MB`...).
+///
+/// The built-in tables cover the labels selected by the `units`, `long_units` and `base_unit` options, plus a compact
+/// single-letter set. Any other table can be plugged with `FormatSizeOptions::unit_table`:
+/// ```rust
+/// use humansize::{format_size, FormatSizeOptions, UnitTable, DECIMAL};
+///
+/// const OCTETS: UnitTable = UnitTable::new(&["o", "Ko", "Mo", "Go", "To"], false);
+/// assert_eq!(format_size(1_500_000u32, FormatSizeOptions::from(DECIMAL).unit_table(Some(OCTETS))), "1.50 Mo");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct UnitTable {
+    /// The label of each unit. Values are never scaled beyond the last one.
+    pub labels: &'static [&'static str],
+    /// Whether the labels are plurals, whose final `s` is removed for values of 1 (e.g. `1 Kilobyte`, `1 bit`).
+    pub plural: bool,
+    /// The singular labels of built-in tables whose plural `s` isn't the last character (e.g. `Kilobytes (1024)`).
+    singular: Option<&'static [&'static str]>,
+}
+
+impl UnitTable {
+    pub const DECIMAL: UnitTable = UnitTable::new(&SCALE_DECIMAL, false);
+    pub const DECIMAL_LONG: UnitTable = UnitTable::new(&SCALE_DECIMAL_LONG, true);
+    pub const BINARY: UnitTable = UnitTable::new(&SCALE_BINARY, false);
+    pub const BINARY_LONG: UnitTable = UnitTable::new(&SCALE_BINARY_LONG, true);
+    pub const DECIMAL_BIT: UnitTable = UnitTable::new(&SCALE_DECIMAL_BIT, true);
+    pub const DECIMAL_BIT_LONG: UnitTable = UnitTable::new(&SCALE_DECIMAL_BIT_LONG, true);
+    pub const BINARY_BIT: UnitTable = UnitTable::new(&SCALE_BINARY_BIT, true);
+    pub const BINARY_BIT_LONG: UnitTable = UnitTable::new(&SCALE_BINARY_BIT_LONG, true);
+    pub const JEDEC: UnitTable = UnitTable::new(&SCALE_JEDEC, false);
+    pub const JEDEC_BIT: UnitTable = UnitTable::new(&SCALE_JEDEC_BIT, true);
+    /// Long JEDEC labels, qualified with their multiplier to tell them apart from the SI ones (e.g. `Kilobytes (1024)`).
+    pub const JEDEC_LONG: UnitTable = UnitTable {
+        labels: &SCALE_JEDEC_LONG,
+        plural: true,
+        singular: Some(&SCALE_JEDEC_LONG_SINGULAR),
+    };
+    pub const JEDEC_BIT_LONG: UnitTable = UnitTable {
+        labels: &SCALE_JEDEC_BIT_LONG,
+        plural: true,
+        singular: Some(&SCALE_JEDEC_BIT_LONG_SINGULAR),
+    };
+    /// Single-letter labels, as displayed by `ls -h` (e.g. `4.0K`, `12M`) when used without a space after the value.
+    pub const COMPACT: UnitTable = UnitTable::new(&SCALE_COMPACT, false);
+
+    pub const fn new(labels: &'static [&'static str], plural: bool) -> UnitTable {
+        UnitTable {
+            labels,
+            plural,
+            singular: None,
+        }
+    }
+
+    /// The built-in table displaying the given set of units.
+    pub(crate) const fn standard(units: Kilo, long_units: bool, base_unit: BaseUnit) -> UnitTable {
+        match (units, long_units, base_unit) {
+            (Kilo::Decimal | Kilo::Custom(_), false, BaseUnit::Byte) => UnitTable::DECIMAL,
+            (Kilo::Decimal | Kilo::Custom(_), true, BaseUnit::Byte) => UnitTable::DECIMAL_LONG,
+            (Kilo::Binary, false, BaseUnit::Byte) => UnitTable::BINARY,
+            (Kilo::Binary, true, BaseUnit::Byte) => UnitTable::BINARY_LONG,
+            (Kilo::Decimal | Kilo::Custom(_), false, BaseUnit::Bit) => UnitTable::DECIMAL_BIT,
+            (Kilo::Decimal | Kilo::Custom(_), true, BaseUnit::Bit) => UnitTable::DECIMAL_BIT_LONG,
+            (Kilo::Binary, false, BaseUnit::Bit) => UnitTable::BINARY_BIT,
+            (Kilo::Binary, true, BaseUnit::Bit) => UnitTable::BINARY_BIT_LONG,
+            (Kilo::Jedec, false, BaseUnit::Byte) => UnitTable::JEDEC,
+            (Kilo::Jedec, false, BaseUnit::Bit) => UnitTable::JEDEC_BIT,
+            (Kilo::Jedec, true, BaseUnit::Byte) => UnitTable::JEDEC_LONG,
+            (Kilo::Jedec, true, BaseUnit::Bit) => UnitTable::JEDEC_BIT_LONG,
+        }
+    }
+
+    /// The label of the unit at `scale_idx` for the given value.
+    pub(crate) fn label(&self, scale_idx: usize, integer_part: f64) -> &'static str {
+        let label = self.labels[scale_idx];
+        if !self.plural || !f64_eq(integer_part, 1.0) {
+            return label;
+        }
+        match self.singular {
+            Some(singular) => singular[scale_idx],
+            None if label.ends_with('s') => &label[0..label.len() - 1],
+            None => label,
+        }
+    }
+}
@@ -0,0 +1,574 @@
+//! Helpers to (de)serialize integer fields as human-readable sizes, available with the `serde` feature.
+//!
+//! Each module formats with the preset it is named after and can be used with `#[serde(with = "...")]`.
+//! Deserialization accepts either a raw integer or any string `parse_size` understands:
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Config {
+//!     #[serde(with = "humansize::serde::binary")]
+//!     max_upload: u64,
+//! }
+//! ```
+//!
+//! Formats which aren't human-readable (e.g. bincode) store the raw integer instead, as do human-readable ones for
+//! sizes the formatted string would round (e.g. 1,500,000 bytes, displayed as `1.43 MiB`).
+//!
+//! `ByteSize` also implements `Serialize` and `Deserialize` with the `BINARY` options, `ByteSizeWith` with the options
+//! of its `OptionsPreset`, and `FormattedSize` implements `Serialize` to send the parts of a formatted size to front-ends styling them.
+//!
+//! `FormatSizeOptions` can itself be loaded from a configuration file, either as the name of a preset
+//! (`"binary"`, `"decimal"`, `"windows"` or `"jedec"`), or as a map of fields overriding a preset (`DECIMAL` if none is given):
+//!
+//! ```rust
+//! use humansize::{format_size, FormatSizeOptions};
+//!
+//! let options: FormatSizeOptions =
+//!     serde_json::from_str(r#"{ "preset": "binary", "decimal_places": 1, "suffix": "/s" }"#).unwrap();
+//! assert_eq!(format_size(1536u32, options), "1.5 KiB/s");
+//! ```
+//!
+//! Since the `suffix` and `placeholder` fields are `&'static str`s, strings other than a few common ones
+//! (`/s`, `/min`, `/h`, `/day` suffixes, `-`, `—`, `n/a` placeholders) are leaked when deserialized,
+//! which is fine for options loaded once at startup.
+//! Without an allocator (`no_alloc`), only those common strings can be deserialized.
+
+use core::fmt::{self, Write};
+use core::marker::PhantomData;
+use core::mem::size_of;
+
+use ::serde::de::{
+    self, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Unexpected, Visitor,
+};
+use ::serde::ser::SerializeStruct;
+use ::serde::{Deserialize, Serialize, Serializer};
+
+use crate::{
+    parse_size, BaseUnit, ByteSize, ByteSizeWith, Digits, FixedAt, FormatSizeOptions,
+    FormattedSize, FromSize, Kilo, OptionsPreset, Overflow, ParseSizeOptions, Rounding, ScaleStep,
+    SizeFormatter, ToF64, UnitTable, Unsigned, BINARY, DECIMAL, JEDEC, WINDOWS,
+};
+
+/// Serializes a size as a string formatted with the given options in human-readable formats, and as an integer
+/// otherwise. Sizes which the formatted string doesn't hold exactly (e.g. 1,500,000 bytes, displayed as `1.43 MiB`)
+/// are serialized as integers as well, or as strings of digits beyond `u64::MAX`, so that they read back unchanged.
+pub fn serialize_with<T, S>(
+    value: &T,
+    serializer: S,
+    options: &FormatSizeOptions,
+) -> Result<S::Ok, S::Error>
+where
+    T: ToF64 + Unsigned + Copy,
+    S: Serializer,
+{
+    let (_, magnitude) = value.to_exact().unwrap_or((false, value.to_f64() as u128));
+    if !serializer.is_human_readable() {
+        return if size_of::<T>() <= size_of::<u64>() {
+            serializer.serialize_u64(magnitude as u64)
+        } else {
+            serializer.serialize_u128(magnitude)
+        };
+    }
+
+    let mut formatted = FormattedBuf::new();
+    if write!(formatted, "{}", SizeFormatter::new(*value, options)).is_ok()
+        && parse_size::<u128>(formatted.as_str(), options) == Ok(magnitude)
+    {
+        serializer.serialize_str(formatted.as_str())
+    } else if magnitude <= u64::MAX as u128 {
+        serializer.serialize_u64(magnitude as u64)
+    } else {
+        // Many human-readable formats (e.g. JSON) don't hold larger integers
+        serializer.collect_str(&magnitude)
+    }
+}
+
+/// Holds a formatted size, failing to write more than it can hold.
+struct FormattedBuf {
+    bytes: [u8; 128],
+    len: usize,
+}
+
+impl FormattedBuf {
+    fn new() -> FormattedBuf {
+        FormattedBuf {
+            bytes: [0; 128],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only whole strings are written
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or_default()
+    }
+}
+
+impl Write for FormattedBuf {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.bytes
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+/// Deserializes a size from a raw integer or a human-readable string parsed with the given options, or from
+/// an integer only in formats which aren't human-readable.
+pub fn deserialize_with<'de, T, D>(
+    deserializer: D,
+    options: impl Into<ParseSizeOptions>,
+) -> Result<T, D::Error>
+where
+    T: FromSize,
+    D: Deserializer<'de>,
+{
+    let visitor = SizeVisitor {
+        options: options.into(),
+        marker: PhantomData,
+    };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_any(visitor)
+    } else if size_of::<T>() <= size_of::<u64>() {
+        deserializer.deserialize_u64(visitor)
+    } else {
+        deserializer.deserialize_u128(visitor)
+    }
+}
+
+struct SizeVisitor<T> {
+    options: ParseSizeOptions,
+    marker: PhantomData<T>,
+}
+
+impl<'de, T: FromSize> Visitor<'de> for SizeVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer or a size such as \"1.5 GiB\"")
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<T, E> {
+        T::from_size(v as u128, 0.0).ok_or_else(|| E::invalid_value(Unexpected::Unsigned(v), &self))
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<T, E> {
+        T::from_size(v, 0.0).ok_or_else(|| E::custom(format_args!("size {} is out of range", v)))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<T, E> {
+        if v < 0 {
+            return Err(E::invalid_value(Unexpected::Signed(v), &self));
+        }
+        self.visit_u64(v as u64)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        parse_size(v, self.options)
+            .map_err(|err| E::custom(format_args!("invalid size {:?}: {}", v, err)))
+    }
+}
+
+macro_rules! preset_module {
+    ($name:ident, $preset:ident) => {
+        #[doc = concat!("(De)serializes sizes with the `", stringify!($preset), "` preset.")]
+        pub mod $name {
+            use ::serde::{Deserializer, Serializer};
+
+            use crate::{$preset, FromSize, ToF64, Unsigned};
+
+            pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                T: ToF64 + Unsigned + Copy,
+                S: Serializer,
+            {
+                super::serialize_with(value, serializer, &$preset)
+            }
+
+            pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+            where
+                T: FromSize,
+                D: Deserializer<'de>,
+            {
+                super::deserialize_with(deserializer, $preset)
+            }
+        }
+    };
+}
+
+preset_module!(binary, BINARY);
+preset_module!(decimal, DECIMAL);
+preset_module!(windows, WINDOWS);
+preset_module!(jedec, JEDEC);
+
+/// Serializes like `serialize_with` with the `BINARY` options.
+impl Serialize for ByteSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_with(&self.0, serializer, &BINARY)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_with(deserializer, BINARY).map(ByteSize)
+    }
+}
+
+/// Serializes like `serialize_with` with the options of `P`.
+impl<P: OptionsPreset> Serialize for ByteSizeWith<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_with(&self.size().0, serializer, &P::OPTIONS)
+    }
+}
+
+impl<'de, P: OptionsPreset> Deserialize<'de> for ByteSizeWith<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_with(deserializer, P::OPTIONS).map(|bytes| ByteSizeWith::new(ByteSize(bytes)))
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Preset {
+    Binary,
+    Decimal,
+    Windows,
+    Jedec,
+}
+
+impl Preset {
+    fn options(self) -> FormatSizeOptions {
+        match self {
+            Preset::Binary => BINARY,
+            Preset::Decimal => DECIMAL,
+            Preset::Windows => WINDOWS,
+            Preset::Jedec => JEDEC,
+        }
+    }
+}
+
+/// Suffixes and placeholders which can be deserialized without allocating.
+const STATIC_STRS: &[&str] = &["", "/s", "/min", "/h", "/day", "-", "\u{2014}", "n/a"];
+
+/// Turns a deserialized suffix or placeholder into the `&'static str` `FormatSizeOptions` holds.
+fn static_str<E: de::Error>(s: &str) -> Result<&'static str, E> {
+    if let Some(known) = STATIC_STRS.iter().find(|known| **known == s) {
+        return Ok(known);
+    }
+
+    #[cfg(not(feature = "no_alloc"))]
+    return Ok(alloc::boxed::Box::leak(
+        alloc::string::String::from(s).into_boxed_str(),
+    ));
+
+    #[cfg(feature = "no_alloc")]
+    Err(E::invalid_value(
+        Unexpected::Str(s),
+        &"a suffix among \"/s\", \"/min\", \"/h\" and \"/day\", or a placeholder among \"-\", \"\u{2014}\" and \"n/a\"",
+    ))
+}
+
+struct StaticStr(&'static str);
+
+impl<'de> Deserialize<'de> for StaticStr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StrVisitor;
+
+        impl<'de> Visitor<'de> for StrVisitor {
+            type Value = StaticStr;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<StaticStr, E> {
+                static_str(v).map(StaticStr)
+            }
+        }
+
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+/// A deserialized sequence of `D`s converted into `T`s, leaked to get the `&'static` slice the options hold.
+struct StaticSlice<T: 'static, D = T>(&'static [T], PhantomData<D>);
+
+impl<'de, T: 'static, D: Deserialize<'de> + Into<T>> Deserialize<'de> for StaticSlice<T, D> {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        struct SliceVisitor<T, D>(PhantomData<(T, D)>);
+
+        impl<'de, T: 'static, D: Deserialize<'de> + Into<T>> Visitor<'de> for SliceVisitor<T, D> {
+            type Value = StaticSlice<T, D>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a sequence")
+            }
+
+            #[cfg(not(feature = "no_alloc"))]
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut elements = alloc::vec::Vec::new();
+                while let Some(element) = seq.next_element::<D>()? {
+                    elements.push(element.into());
+                }
+                Ok(StaticSlice(
+                    alloc::boxed::Box::leak(elements.into_boxed_slice()),
+                    PhantomData,
+                ))
+            }
+
+            #[cfg(feature = "no_alloc")]
+            fn visit_seq<A: SeqAccess<'de>>(self, _seq: A) -> Result<Self::Value, A::Error> {
+                Err(de::Error::custom(
+                    "sequences can't be deserialized without an allocator",
+                ))
+            }
+        }
+
+        deserializer.deserialize_seq(SliceVisitor(PhantomData))
+    }
+}
+
+impl From<StaticStr> for &'static str {
+    fn from(s: StaticStr) -> Self {
+        s.0
+    }
+}
+
+#[derive(Deserialize)]
+struct LadderStep {
+    multiplier: u128,
+    label: StaticStr,
+}
+
+impl From<LadderStep> for ScaleStep {
+    fn from(step: LadderStep) -> Self {
+        ScaleStep::new(step.multiplier, step.label.0)
+    }
+}
+
+const UNIT_TABLES: &[(&str, UnitTable)] = &[
+    ("decimal", UnitTable::DECIMAL),
+    ("decimal_long", UnitTable::DECIMAL_LONG),
+    ("binary", UnitTable::BINARY),
+    ("binary_long", UnitTable::BINARY_LONG),
+    ("decimal_bit", UnitTable::DECIMAL_BIT),
+    ("decimal_bit_long", UnitTable::DECIMAL_BIT_LONG),
+    ("binary_bit", UnitTable::BINARY_BIT),
+    ("binary_bit_long", UnitTable::BINARY_BIT_LONG),
+    ("jedec", UnitTable::JEDEC),
+    ("jedec_bit", UnitTable::JEDEC_BIT),
+    ("jedec_long", UnitTable::JEDEC_LONG),
+    ("jedec_bit_long", UnitTable::JEDEC_BIT_LONG),
+    ("compact", UnitTable::COMPACT),
+];
+
+/// Serializes built-in tables by name (e.g. `"compact"`), and other tables as a map of their fields.
+impl Serialize for UnitTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if let Some((name, _)) = UNIT_TABLES.iter().find(|(_, table)| table == self) {
+            return serializer.serialize_str(name);
+        }
+
+        let mut state = serializer.serialize_struct("UnitTable", 2)?;
+        state.serialize_field("labels", self.labels)?;
+        state.serialize_field("plural", &self.plural)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+struct CustomUnitTable {
+    labels: StaticSlice<&'static str, StaticStr>,
+    #[serde(default)]
+    plural: bool,
+}
+
+/// Deserializes a built-in table from its name, or any table from a map of its fields.
+impl<'de> Deserialize<'de> for UnitTable {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct UnitTableVisitor;
+
+        impl<'de> Visitor<'de> for UnitTableVisitor {
+            type Value = UnitTable;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("the name of a unit table or a map of unit labels")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<UnitTable, E> {
+                match UNIT_TABLES.iter().find(|(name, _)| *name == v) {
+                    Some((_, table)) => Ok(*table),
+                    None => Err(E::invalid_value(
+                        Unexpected::Str(v),
+                        &"the name of a built-in unit table",
+                    )),
+                }
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<UnitTable, A::Error> {
+                let table =
+                    CustomUnitTable::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(UnitTable::new(table.labels.0, table.plural))
+            }
+        }
+
+        deserializer.deserialize_any(UnitTableVisitor)
+    }
+}
+
+/// Serializes the digits as a string.
+impl Serialize for Digits {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Serializes the parts of a formatted size, including the `integer` and `fraction` digits and the
+/// `separator_positions`.
+impl Serialize for FormattedSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct SeparatorPositions<'a>(&'a FormattedSize);
+
+        impl Serialize for SeparatorPositions<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.separator_positions())
+            }
+        }
+
+        let mut state = serializer.serialize_struct("FormattedSize", 14)?;
+        state.serialize_field("placeholder", &self.placeholder)?;
+        state.serialize_field("bound", &self.bound)?;
+        state.serialize_field("negative", &self.negative)?;
+        state.serialize_field("non_finite", &self.non_finite)?;
+        state.serialize_field("integer", &self.integer())?;
+        state.serialize_field("separator", &self.separator)?;
+        state.serialize_field("separator_positions", &SeparatorPositions(self))?;
+        state.serialize_field("fraction", &self.fraction())?;
+        state.serialize_field("exponent", &self.exponent)?;
+        state.serialize_field("space", &self.space)?;
+        state.serialize_field("unit", self.unit)?;
+        state.serialize_field("suffix", self.suffix)?;
+        state.serialize_field("scale", &self.scale)?;
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
+
+/// Lets a `null` override a field with `None`, which a plain `Option` would read as a missing field.
+fn nullable<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    deserializer: D,
+) -> Result<Option<Option<T>>, D::Error> {
+    Option::deserialize(deserializer).map(Some)
+}
+
+/// The fields of a map of options, overriding those of the preset once the whole map is read.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Overrides {
+    preset: Option<Preset>,
+    base_unit: Option<BaseUnit>,
+    kilo: Option<Kilo>,
+    units: Option<Kilo>,
+    decimal_places: Option<usize>,
+    decimal_zeroes: Option<usize>,
+    #[serde(deserialize_with = "nullable")]
+    fixed_at: Option<Option<FixedAt>>,
+    long_units: Option<bool>,
+    space_after_value: Option<bool>,
+    suffix: Option<StaticStr>,
+    #[serde(deserialize_with = "nullable")]
+    thousands_separator: Option<Option<char>>,
+    #[serde(deserialize_with = "nullable")]
+    significant_digits: Option<Option<usize>>,
+    rounding: Option<Rounding>,
+    #[serde(deserialize_with = "nullable")]
+    rollover_threshold: Option<Option<u32>>,
+    #[serde(deserialize_with = "nullable")]
+    placeholder: Option<Option<StaticStr>>,
+    extended_prefixes: Option<bool>,
+    overflow: Option<Overflow>,
+    #[serde(deserialize_with = "nullable")]
+    scale_ladder: Option<Option<StaticSlice<ScaleStep, LadderStep>>>,
+    #[serde(deserialize_with = "nullable")]
+    unit_table: Option<Option<UnitTable>>,
+    #[serde(deserialize_with = "nullable")]
+    min_unit: Option<Option<FixedAt>>,
+    #[serde(deserialize_with = "nullable")]
+    max_unit: Option<Option<FixedAt>>,
+    #[serde(deserialize_with = "nullable")]
+    unit_decimal_places: Option<Option<StaticSlice<usize>>>,
+    #[serde(deserialize_with = "nullable")]
+    min_fraction_digits: Option<Option<usize>>,
+    #[serde(deserialize_with = "nullable")]
+    max_fraction_digits: Option<Option<usize>>,
+}
+
+impl Overrides {
+    fn apply(self) -> FormatSizeOptions {
+        let base = self.preset.map_or(DECIMAL, Preset::options);
+        FormatSizeOptions {
+            base_unit: self.base_unit.unwrap_or(base.base_unit),
+            kilo: self.kilo.unwrap_or(base.kilo),
+            units: self.units.unwrap_or(base.units),
+            decimal_places: self.decimal_places.unwrap_or(base.decimal_places),
+            decimal_zeroes: self.decimal_zeroes.unwrap_or(base.decimal_zeroes),
+            fixed_at: self.fixed_at.unwrap_or(base.fixed_at),
+            long_units: self.long_units.unwrap_or(base.long_units),
+            space_after_value: self.space_after_value.unwrap_or(base.space_after_value),
+            suffix: self.suffix.map_or(base.suffix, |suffix| suffix.0),
+            thousands_separator: self.thousands_separator.unwrap_or(base.thousands_separator),
+            significant_digits: self.significant_digits.unwrap_or(base.significant_digits),
+            rounding: self.rounding.unwrap_or(base.rounding),
+            rollover_threshold: self.rollover_threshold.unwrap_or(base.rollover_threshold),
+            placeholder: self
+                .placeholder
+                .map_or(base.placeholder, |placeholder| placeholder.map(|p| p.0)),
+            extended_prefixes: self.extended_prefixes.unwrap_or(base.extended_prefixes),
+            overflow: self.overflow.unwrap_or(base.overflow),
+            scale_ladder: self
+                .scale_ladder
+                .map_or(base.scale_ladder, |ladder| ladder.map(|l| l.0)),
+            unit_table: self.unit_table.unwrap_or(base.unit_table),
+            min_unit: self.min_unit.unwrap_or(base.min_unit),
+            max_unit: self.max_unit.unwrap_or(base.max_unit),
+            unit_decimal_places: self
+                .unit_decimal_places
+                .map_or(base.unit_decimal_places, |places| places.map(|p| p.0)),
+            min_fraction_digits: self.min_fraction_digits.unwrap_or(base.min_fraction_digits),
+            max_fraction_digits: self.max_fraction_digits.unwrap_or(base.max_fraction_digits),
+        }
+    }
+}
+
+/// Deserializes a preset from its name, or a map of fields overriding a preset (`DECIMAL` if none is given).
+/// The options are validated.
+impl<'de> Deserialize<'de> for FormatSizeOptions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct OptionsVisitor;
+
+        impl<'de> Visitor<'de> for OptionsVisitor {
+            type Value = FormatSizeOptions;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a preset name or a map of formatting options")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<FormatSizeOptions, E> {
+                Preset::deserialize(v.into_deserializer()).map(Preset::options)
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<FormatSizeOptions, A::Error> {
+                let options =
+                    Overrides::deserialize(de::value::MapAccessDeserializer::new(map))?.apply();
+                options.validate().map_err(de::Error::custom)?;
+                Ok(options)
+            }
+        }
+
+        deserializer.deserialize_any(OptionsVisitor)
+    }
+}
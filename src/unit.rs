@@ -0,0 +1,238 @@
+//! Describes `Unit`, the units of information the formatters display, along with their labels and sizes.
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::parse::find_unit;
+use crate::{scales, BaseUnit, FixedAt, Kilo, ParseSizeError};
+
+/// A unit of information: bytes or bits, scaled by a decimal (SI) or binary (IEC) prefix.
+///
+/// ```rust
+/// use humansize::{BaseUnit, FixedAt, Kilo, Unit};
+///
+/// assert_eq!(Unit::Mebibyte.multiplier(), 1_048_576);
+/// assert_eq!(Unit::Gigabyte.long_name(), "Gigabyte");
+/// assert_eq!("KiB".parse::<Unit>(), Ok(Unit::Kibibyte));
+/// assert_eq!(Unit::new(FixedAt::Tera, Kilo::Decimal, BaseUnit::Bit), Unit::Terabit);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Unit {
+    Byte,
+    Kilobyte,
+    Megabyte,
+    Gigabyte,
+    Terabyte,
+    Petabyte,
+    Exabyte,
+    Zettabyte,
+    Yottabyte,
+    Ronnabyte,
+    Quettabyte,
+    Kibibyte,
+    Mebibyte,
+    Gibibyte,
+    Tebibyte,
+    Pebibyte,
+    Exbibyte,
+    Zebibyte,
+    Yobibyte,
+    Robibyte,
+    Quebibyte,
+    Bit,
+    Kilobit,
+    Megabit,
+    Gigabit,
+    Terabit,
+    Petabit,
+    Exabit,
+    Zettabit,
+    Yottabit,
+    Ronnabit,
+    Quettabit,
+    Kibibit,
+    Mebibit,
+    Gibibit,
+    Tebibit,
+    Pebibit,
+    Exbibit,
+    Zebibit,
+    Yobibit,
+    Robibit,
+    Quebibit,
+}
+
+/// Every unit, in declaration order: decimal bytes from the base unit up, binary bytes from kibi up,
+/// then the same for bits.
+const UNITS: [Unit; 42] = [
+    Unit::Byte,
+    Unit::Kilobyte,
+    Unit::Megabyte,
+    Unit::Gigabyte,
+    Unit::Terabyte,
+    Unit::Petabyte,
+    Unit::Exabyte,
+    Unit::Zettabyte,
+    Unit::Yottabyte,
+    Unit::Ronnabyte,
+    Unit::Quettabyte,
+    Unit::Kibibyte,
+    Unit::Mebibyte,
+    Unit::Gibibyte,
+    Unit::Tebibyte,
+    Unit::Pebibyte,
+    Unit::Exbibyte,
+    Unit::Zebibyte,
+    Unit::Yobibyte,
+    Unit::Robibyte,
+    Unit::Quebibyte,
+    Unit::Bit,
+    Unit::Kilobit,
+    Unit::Megabit,
+    Unit::Gigabit,
+    Unit::Terabit,
+    Unit::Petabit,
+    Unit::Exabit,
+    Unit::Zettabit,
+    Unit::Yottabit,
+    Unit::Ronnabit,
+    Unit::Quettabit,
+    Unit::Kibibit,
+    Unit::Mebibit,
+    Unit::Gibibit,
+    Unit::Tebibit,
+    Unit::Pebibit,
+    Unit::Exbibit,
+    Unit::Zebibit,
+    Unit::Yobibit,
+    Unit::Robibit,
+    Unit::Quebibit,
+];
+
+/// Binary units are numbered from kibi, after the base and decimal units of the same base unit.
+const BINARY_OFFSET: usize = scales::SCALE_LENGTH - 1;
+const BIT_OFFSET: usize = 2 * scales::SCALE_LENGTH - 1;
+
+impl Unit {
+    /// The unit at a scale of a set of units. The base unit is shared by both sets, JEDEC units are
+    /// the binary ones, and a custom kilo uses the decimal ones.
+    pub const fn new(scale: FixedAt, kilo: Kilo, base_unit: BaseUnit) -> Unit {
+        let scale_idx = scale as usize;
+        let binary = matches!(kilo, Kilo::Binary | Kilo::Jedec) && scale_idx > 0;
+        let offset = match base_unit {
+            BaseUnit::Byte => 0,
+            BaseUnit::Bit => BIT_OFFSET,
+        };
+        if binary {
+            UNITS[offset + BINARY_OFFSET + scale_idx]
+        } else {
+            UNITS[offset + scale_idx]
+        }
+    }
+
+    /// Returns the index of the unit in its set, whether it is binary, and its base unit.
+    const fn decompose(self) -> (usize, bool, BaseUnit) {
+        let (idx, base_unit) = if (self as usize) < BIT_OFFSET {
+            (self as usize, BaseUnit::Byte)
+        } else {
+            (self as usize - BIT_OFFSET, BaseUnit::Bit)
+        };
+        if idx < scales::SCALE_LENGTH {
+            (idx, false, base_unit)
+        } else {
+            (idx - BINARY_OFFSET, true, base_unit)
+        }
+    }
+
+    /// The scale of the unit (e.g. `FixedAt::Mega` for `Unit::Mebibyte`).
+    pub const fn scale(self) -> FixedAt {
+        FixedAt::ALL[self.decompose().0]
+    }
+
+    /// The set of units it belongs to: `Kilo::Binary` for binary units, `Kilo::Decimal` for the others,
+    /// including `Unit::Byte` and `Unit::Bit`.
+    pub const fn kilo(self) -> Kilo {
+        if self.decompose().1 {
+            Kilo::Binary
+        } else {
+            Kilo::Decimal
+        }
+    }
+
+    pub const fn base_unit(self) -> BaseUnit {
+        self.decompose().2
+    }
+
+    /// The amount of bytes, or bits for bit units, in the unit.
+    pub const fn multiplier(self) -> u128 {
+        let kilo: u128 = if self.decompose().1 { 1024 } else { 1000 };
+        kilo.pow(self.decompose().0 as u32)
+    }
+
+    /// The short label the formatters display (e.g. `KiB`, `kbit`, `bits`).
+    pub const fn symbol(self) -> &'static str {
+        self.labels(false)[self.decompose().0]
+    }
+
+    /// The long label in its singular form (e.g. `Kibibyte`).
+    pub fn long_name(self) -> &'static str {
+        let plural = self.plural();
+        &plural[..plural.len() - 1]
+    }
+
+    /// The long label in its plural form, as the formatters display it (e.g. `Kibibytes`).
+    pub const fn plural(self) -> &'static str {
+        self.labels(true)[self.decompose().0]
+    }
+
+    const fn labels(self, long: bool) -> &'static [&'static str; scales::SCALE_LENGTH] {
+        match (self.decompose(), long) {
+            ((_, false, BaseUnit::Byte), false) => &scales::SCALE_DECIMAL,
+            ((_, false, BaseUnit::Byte), true) => &scales::SCALE_DECIMAL_LONG,
+            ((_, true, BaseUnit::Byte), false) => &scales::SCALE_BINARY,
+            ((_, true, BaseUnit::Byte), true) => &scales::SCALE_BINARY_LONG,
+            ((_, false, BaseUnit::Bit), false) => &scales::SCALE_DECIMAL_BIT,
+            ((_, false, BaseUnit::Bit), true) => &scales::SCALE_DECIMAL_BIT_LONG,
+            ((_, true, BaseUnit::Bit), false) => &scales::SCALE_BINARY_BIT,
+            ((_, true, BaseUnit::Bit), true) => &scales::SCALE_BINARY_BIT_LONG,
+        }
+    }
+}
+
+impl From<Unit> for FixedAt {
+    fn from(unit: Unit) -> FixedAt {
+        unit.scale()
+    }
+}
+
+/// Displays the symbol of the unit, or its plural long name with the alternate flag (`{:#}`).
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = if f.alternate() {
+            self.plural()
+        } else {
+            self.symbol()
+        };
+        f.pad(label)
+    }
+}
+
+/// Parses any label `parse_size` understands, in its short, long, singular or plural form. JEDEC labels
+/// (e.g. `KB`) are read as binary units.
+impl FromStr for Unit {
+    type Err = ParseSizeError;
+
+    fn from_str(s: &str) -> Result<Unit, ParseSizeError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseSizeError::Empty);
+        }
+        let (kilo, base_unit, scale_idx) = find_unit(s).ok_or(ParseSizeError::UnknownUnit)?;
+        Ok(Unit::new(FixedAt::ALL[scale_idx], kilo, base_unit))
+    }
+}
@@ -1,5 +1,17 @@
+use core::fmt;
+
 use libm::fabs;
 
 pub(crate) fn f64_eq(left: f64, right: f64) -> bool {
     left == right || fabs(left - right) <= f64::EPSILON
 }
+
+/// A writer which only counts the characters written to it, to compute padding without allocating.
+pub(crate) struct CharCounter(pub(crate) usize);
+
+impl fmt::Write for CharCounter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
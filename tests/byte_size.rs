@@ -0,0 +1,91 @@
+use humansize::{
+    format_size, presets, ByteSize, ByteSizeWith, FormatSizeOptions, OptionsPreset, ParseSizeError,
+    DECIMAL, WINDOWS,
+};
+
+#[test]
+fn constructors() {
+    assert_eq!(ByteSize::b(512).as_u64(), 512);
+    assert_eq!(ByteSize::kb(4), ByteSize(4_000));
+    assert_eq!(ByteSize::kib(4), ByteSize(4_096));
+    assert_eq!(ByteSize::gb(2), ByteSize(2_000_000_000));
+    assert_eq!(ByteSize::gib(2), ByteSize(2_147_483_648));
+    assert_eq!(ByteSize::eib(32), ByteSize::MAX);
+}
+
+#[test]
+fn arithmetic() {
+    let mut size = ByteSize::kib(1) + ByteSize::b(512);
+    assert_eq!(size, ByteSize(1536));
+
+    size -= ByteSize::b(512);
+    size *= 2;
+    assert_eq!(size, ByteSize::kib(2));
+    assert_eq!(size / 4, ByteSize(512));
+    assert_eq!(3 * ByteSize::mb(1), ByteSize::mb(3));
+
+    assert_eq!(ByteSize::MAX.checked_add(ByteSize(1)), None);
+    assert_eq!(ByteSize::ZERO.checked_sub(ByteSize(1)), None);
+    assert_eq!(ByteSize::MAX.checked_mul(2), None);
+    assert_eq!(ByteSize(1).checked_div(0), None);
+    assert_eq!(ByteSize::MAX.saturating_add(ByteSize(1)), ByteSize::MAX);
+    assert_eq!(ByteSize::ZERO.saturating_sub(ByteSize(1)), ByteSize::ZERO);
+    assert_eq!(ByteSize::MAX.saturating_mul(2), ByteSize::MAX);
+
+    let sizes = [ByteSize::kib(1), ByteSize::kib(2), ByteSize::kib(3)];
+    assert_eq!(sizes.iter().sum::<ByteSize>(), ByteSize::kib(6));
+    assert_eq!(sizes.iter().max(), Some(&ByteSize::kib(3)));
+    assert!(ByteSize::kb(1) < ByteSize::kib(1));
+}
+
+#[test]
+fn display_and_parse() {
+    assert_eq!(ByteSize::kib(4).to_string(), "4 KiB");
+    assert_eq!(ByteSize::gb(2).display(DECIMAL).to_string(), "2 GB");
+    assert_eq!(format_size(ByteSize::mb(1), DECIMAL), "1 MB");
+
+    assert_eq!("4 KiB".parse(), Ok(ByteSize::kib(4)));
+    assert_eq!("2 GB".parse(), Ok(ByteSize::gb(2)));
+    assert_eq!(
+        "lots".parse::<ByteSize>(),
+        Err(ParseSizeError::InvalidNumber)
+    );
+    assert_eq!(ByteSize::parse_with("4 kB", WINDOWS), Ok(ByteSize::kib(4)));
+    assert_eq!(ByteSize::kib(4).display(WINDOWS).to_string(), "4 kB");
+
+    for size in [
+        ByteSize::b(17),
+        ByteSize::kib(4),
+        ByteSize::mib(3) + ByteSize::kib(512),
+    ] {
+        assert_eq!(size.to_string().parse(), Ok(size));
+    }
+}
+
+#[test]
+fn default_options() {
+    struct Storage;
+
+    impl OptionsPreset for Storage {
+        const OPTIONS: FormatSizeOptions = DECIMAL.decimal_places(1).space_after_value(false);
+    }
+
+    let size: ByteSizeWith<Storage> = ByteSize::kb(1500).with_options();
+    assert_eq!(size.to_string(), "1.5MB");
+    assert_eq!(format!("{:>7}", size), "  1.5MB");
+    assert_eq!("1.5MB".parse::<ByteSizeWith<Storage>>(), Ok(size));
+    assert_eq!(size.size(), ByteSize::kb(1500));
+    assert_eq!(size.as_u64(), 1_500_000);
+
+    let windows = ByteSizeWith::<presets::Windows>::new(ByteSize::kib(4));
+    assert_eq!(windows.to_string(), "4 kB");
+    assert_eq!("4 kB".parse(), Ok(windows));
+    assert_eq!(ByteSize::from(windows), ByteSize::kib(4));
+    assert_eq!(
+        ByteSizeWith::<presets::Binary>::default().to_string(),
+        "0 B"
+    );
+    assert!(
+        ByteSizeWith::<presets::Jedec>::from(ByteSize::kb(1)) < ByteSize::kib(1).with_options()
+    );
+}
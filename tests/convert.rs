@@ -0,0 +1,112 @@
+use humansize::{
+    convert, convert_exact, convert_formatter, format_converted, FixedAt, FormatSizeOptions, Unit,
+    BINARY, DECIMAL, JEDEC,
+};
+
+#[test]
+fn convert_values() {
+    assert_eq!(
+        convert(500u32, Unit::Gigabyte, Unit::Gibibyte),
+        465.66128730773926
+    );
+    assert_eq!(convert(1u32, Unit::Gibibyte, Unit::Byte), 1_073_741_824.0);
+    assert_eq!(
+        convert(8u32, Unit::Gigabit, Unit::Mebibyte),
+        953.67431640625
+    );
+    assert_eq!(convert(1u32, Unit::Byte, Unit::Bit), 8.0);
+    assert_eq!(convert(1u32, Unit::Bit, Unit::Byte), 0.125);
+    assert_eq!(convert(-3, Unit::Kibibyte, Unit::Byte), -3072.0);
+    assert_eq!(convert(0.5, Unit::Megabyte, Unit::Kilobyte), 500.0);
+    assert_eq!(convert(7u32, Unit::Mebibyte, Unit::Mebibyte), 7.0);
+    assert_eq!(convert(1u32, Unit::Quebibyte, Unit::Bit), 2f64.powi(103));
+    // Products beyond a u128 go through floats
+    assert_eq!(
+        convert(u128::MAX, Unit::Quettabyte, Unit::Byte),
+        u128::MAX as f64 * 1e30
+    );
+}
+
+#[test]
+fn convert_exact_values() {
+    assert_eq!(
+        convert_exact(500, Unit::Gigabyte, Unit::Megabyte),
+        Some(500_000)
+    );
+    assert_eq!(
+        convert_exact(3, Unit::Gibibyte, Unit::Kibibyte),
+        Some(3 << 20)
+    );
+    assert_eq!(convert_exact(2048, Unit::Kibibyte, Unit::Mebibyte), Some(2));
+    assert_eq!(convert_exact(8, Unit::Gigabit, Unit::Megabyte), Some(1000));
+    assert_eq!(convert_exact(4, Unit::Bit, Unit::Byte), None);
+    assert_eq!(convert_exact(16, Unit::Bit, Unit::Byte), Some(2));
+    assert_eq!(convert_exact(500, Unit::Gigabyte, Unit::Gibibyte), None);
+    assert_eq!(convert_exact(u128::MAX, Unit::Kilobyte, Unit::Byte), None);
+    assert_eq!(convert_exact(0, Unit::Quettabit, Unit::Byte), Some(0));
+}
+
+#[test]
+fn formatted_conversions() {
+    assert_eq!(
+        format_converted(500u32, Unit::Gigabyte, Unit::Gibibyte, DECIMAL),
+        "465.66 GiB"
+    );
+    assert_eq!(
+        format_converted(8u32, Unit::Gigabit, Unit::Mebibyte, BINARY),
+        "953.67 MiB"
+    );
+    assert_eq!(
+        format_converted(1u32, Unit::Tebibyte, Unit::Gigabyte, BINARY),
+        "1099.51 GB"
+    );
+    assert_eq!(
+        format_converted(1u32, Unit::Gigabyte, Unit::Megabit, DECIMAL),
+        "8000 Mbit"
+    );
+    assert_eq!(
+        format_converted(3u32, Unit::Byte, Unit::Bit, DECIMAL),
+        "24 bits"
+    );
+    // Options other than the unit apply
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .decimal_places(1)
+        .thousands_separator(Some(','))
+        .long_units(true);
+    assert_eq!(
+        format_converted(5u32, Unit::Terabyte, Unit::Mebibyte, OPTIONS),
+        "4,768,371.6 Mebibytes"
+    );
+    assert_eq!(
+        format_converted(2u32, Unit::Gibibyte, Unit::Kibibyte, JEDEC),
+        "2097152 KB"
+    );
+
+    // Whole amounts are formatted exactly, as integer inputs are
+    assert_eq!(
+        format_converted(
+            u64::MAX,
+            Unit::Byte,
+            Unit::Byte,
+            BINARY.fixed_at(Some(FixedAt::Base))
+        ),
+        "18446744073709551615 B"
+    );
+    assert_eq!(
+        format_converted(
+            u128::MAX / 8,
+            Unit::Byte,
+            Unit::Bit,
+            DECIMAL.decimal_places(0)
+        ),
+        format!("{} bits", u128::MAX / 8 * 8)
+    );
+    assert_eq!(
+        format_converted(-3i32, Unit::Kibibyte, Unit::Kilobyte, DECIMAL),
+        "-3.07 kB"
+    );
+
+    let formatter = convert_formatter(1536u32, Unit::Kibibyte, Unit::Mebibyte, BINARY);
+    assert_eq!(format!("{:.1}", formatter), "1.5 MiB");
+    assert_eq!(formatter.parts().scale, 2);
+}
@@ -0,0 +1,166 @@
+use humansize::{
+    format_size, parse_size, BaseUnit, FixedAt, FormatSizeOptions, Kilo, ParseSizeError,
+    ParseSizeOptions, BINARY, DECIMAL, JEDEC, WINDOWS,
+};
+
+#[test]
+fn parse_units() {
+    const OPTIONS: ParseSizeOptions = ParseSizeOptions::new();
+
+    assert_eq!(parse_size::<u64>("0", OPTIONS), Ok(0));
+    assert_eq!(parse_size::<u64>("512", OPTIONS), Ok(512));
+    assert_eq!(parse_size::<u64>("512 B", OPTIONS), Ok(512));
+    assert_eq!(parse_size::<u64>("1 kB", OPTIONS), Ok(1000));
+    assert_eq!(parse_size::<u64>("1.5 GiB", OPTIONS), Ok(1_610_612_736));
+    assert_eq!(parse_size::<u64>("1.5GiB", OPTIONS), Ok(1_610_612_736));
+    assert_eq!(parse_size::<u64>("  2 MIB ", OPTIONS), Ok(2_097_152));
+    assert_eq!(parse_size::<u64>("1 Kilobyte", OPTIONS), Ok(1000));
+    assert_eq!(parse_size::<u64>("3 Mebibytes", OPTIONS), Ok(3_145_728));
+    assert_eq!(parse_size::<u64>("8 bits", OPTIONS), Ok(1));
+    assert_eq!(parse_size::<u64>("1 kbit", OPTIONS), Ok(125));
+    assert_eq!(parse_size::<u64>("1 Kibibit", OPTIONS), Ok(128));
+    assert_eq!(parse_size::<u128>("1 YiB", OPTIONS), Ok(1 << 80));
+    assert_eq!(parse_size::<f64>("1 bit", OPTIONS), Ok(0.125));
+
+    // `b` stands for bits and `B` for bytes, whatever the case of the prefix
+    assert_eq!(parse_size::<u64>("100 Mb", OPTIONS), Ok(12_500_000));
+    assert_eq!(parse_size::<u64>("100 MB", OPTIONS), Ok(100_000_000));
+    assert_eq!(parse_size::<u64>("1 kb", OPTIONS), Ok(125));
+    assert_eq!(parse_size::<u64>("1 Kb", OPTIONS), Ok(128));
+    assert_eq!(parse_size::<u64>("1 KB", OPTIONS), Ok(1024));
+    assert_eq!(parse_size::<u64>("1 kB", OPTIONS), Ok(1000));
+    assert_eq!(parse_size::<u64>("2 mib", OPTIONS), Ok(262_144));
+    assert_eq!(parse_size::<u64>("16 b", OPTIONS), Ok(2));
+    assert_eq!(parse_size::<u64>("16 B", OPTIONS), Ok(16));
+}
+
+#[test]
+fn parse_with_options() {
+    assert_eq!(parse_size::<u64>("1 kB", WINDOWS), Ok(1024));
+    assert_eq!(parse_size::<u64>("1 KiB", DECIMAL), Ok(1024));
+
+    const BITS: ParseSizeOptions = ParseSizeOptions::new().base_unit(BaseUnit::Bit);
+    assert_eq!(parse_size::<u64>("1 kB", BITS), Ok(8000));
+    assert_eq!(parse_size::<u64>("3 Mbit", BITS), Ok(3_000_000));
+
+    const SEPARATED: ParseSizeOptions = ParseSizeOptions::new()
+        .kilo(Kilo::Binary)
+        .thousands_separator(Some(','));
+    assert_eq!(parse_size::<u64>("1,024 kB", SEPARATED), Ok(1_048_576));
+    assert_eq!(
+        parse_size::<u64>("1,234,567.25 B", SEPARATED),
+        Ok(1_234_567)
+    );
+    for input in [
+        "1,0,0 B",
+        "1000,000 B",
+        "1,00 B",
+        "1, B",
+        "1,000,00.5 B",
+        "1.000,5 B",
+    ] {
+        assert_eq!(
+            parse_size::<u64>(input, SEPARATED),
+            Err(ParseSizeError::InvalidNumber),
+            "{}",
+            input
+        );
+    }
+
+    // JEDEC-only labels are 1024-based, shared labels follow the options
+    assert_eq!(parse_size::<u64>("1 KB", ParseSizeOptions::new()), Ok(1024));
+    assert_eq!(
+        parse_size::<u64>("2 MB", ParseSizeOptions::new()),
+        Ok(2_000_000)
+    );
+    assert_eq!(parse_size::<u64>("2 MB", JEDEC), Ok(2_097_152));
+    assert_eq!(parse_size::<u64>("1 Kbit", JEDEC), Ok(128));
+    assert_eq!(
+        parse_size::<u64>("2 Kilobytes (1024)", ParseSizeOptions::new()),
+        Ok(2048)
+    );
+    assert_eq!(
+        parse_size::<u64>("1.5 megabyte (1024²)", ParseSizeOptions::new()),
+        Ok(1_572_864)
+    );
+}
+
+#[test]
+fn parse_round_trip() {
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .fixed_at(Some(FixedAt::Kilo))
+        .thousands_separator(Some('_'));
+
+    let formatted = format_size(16_777_216u64, OPTIONS);
+    assert_eq!(parse_size::<u64>(&formatted, OPTIONS), Ok(16_777_216));
+}
+
+#[test]
+fn parse_errors() {
+    const OPTIONS: ParseSizeOptions = ParseSizeOptions::new();
+
+    assert_eq!(parse_size::<u64>("", OPTIONS), Err(ParseSizeError::Empty));
+    assert_eq!(
+        parse_size::<u64>("   ", OPTIONS),
+        Err(ParseSizeError::Empty)
+    );
+    assert_eq!(
+        parse_size::<u64>("GiB", OPTIONS),
+        Err(ParseSizeError::InvalidNumber)
+    );
+    assert_eq!(
+        parse_size::<u64>("-1 GiB", OPTIONS),
+        Err(ParseSizeError::InvalidNumber)
+    );
+    assert_eq!(
+        parse_size::<u64>("1.2.3 GiB", OPTIONS),
+        Err(ParseSizeError::InvalidNumber)
+    );
+    assert_eq!(
+        parse_size::<u64>("12 parsecs", OPTIONS),
+        Err(ParseSizeError::UnknownUnit)
+    );
+    assert_eq!(
+        parse_size::<u32>("5 GB", OPTIONS),
+        Err(ParseSizeError::Overflow)
+    );
+    assert_eq!(
+        parse_size::<u64>("1000000 YB", OPTIONS),
+        Err(ParseSizeError::Overflow)
+    );
+}
+
+#[test]
+fn parse_custom_kilo() {
+    const BLOCKS: ParseSizeOptions = ParseSizeOptions::new().kilo(Kilo::Custom(512));
+    assert_eq!(parse_size::<u64>("3 kB", BLOCKS), Ok(1536));
+    assert_eq!(parse_size::<u64>("1 KiB", BLOCKS), Ok(1024));
+
+    const HUGE: ParseSizeOptions = ParseSizeOptions::new().kilo(Kilo::Custom(1_000_000));
+    assert_eq!(
+        parse_size::<u64>("1 QB", HUGE),
+        Err(ParseSizeError::Overflow)
+    );
+    assert_eq!(
+        parse_size::<u128>("1 QB", HUGE.base_unit(BaseUnit::Bit)),
+        Err(ParseSizeError::Overflow)
+    );
+
+    // Kilos below 2 fall back to the decimal one, as when formatting
+    for kilo in [0, 1] {
+        let options = ParseSizeOptions::new().kilo(Kilo::Custom(kilo));
+        assert_eq!(parse_size::<u64>("1 kB", options), Ok(1000));
+    }
+}
+
+#[test]
+fn parse_extended_prefixes() {
+    assert_eq!(
+        parse_size::<u128>("1 RB", ParseSizeOptions::new()),
+        Ok(10u128.pow(27))
+    );
+    assert_eq!(
+        parse_size::<u128>("2 Quebibytes", ParseSizeOptions::new()),
+        Ok(1 << 101)
+    );
+}
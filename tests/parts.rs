@@ -0,0 +1,147 @@
+use humansize::{
+    format_size, format_size_i, Bound, FixedAt, FormatSizeOptions, ISizeFormatter, NonFinite,
+    Overflow, SizeFormatter, BINARY, DECIMAL,
+};
+
+#[test]
+fn number_and_unit() {
+    let parts = SizeFormatter::new(1536u32, BINARY).parts();
+    assert_eq!(parts.integer().to_string(), "1");
+    assert_eq!(parts.fraction().to_string(), "50");
+    assert!(!parts.negative);
+    assert_eq!(parts.bound, None);
+    assert_eq!(parts.exponent, None);
+    assert!(parts.space);
+    assert_eq!(parts.unit, "KiB");
+    assert_eq!(parts.suffix, "");
+    assert_eq!(parts.scale, 1);
+    assert_eq!(parts.value, 1.5);
+    assert_eq!(parts.to_string(), "1.50 KiB");
+
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .space_after_value(false)
+        .suffix("/s")
+        .decimal_places(1);
+    let parts = ISizeFormatter::new(-2_500_000, OPTIONS).parts();
+    assert!(parts.negative);
+    assert_eq!(parts.integer().to_string(), "2");
+    assert_eq!(parts.fraction().to_string(), "5");
+    assert!(!parts.space);
+    assert_eq!(parts.unit, "MB");
+    assert_eq!(parts.suffix, "/s");
+    assert_eq!(parts.scale, FixedAt::Mega as usize);
+    assert_eq!(parts.to_string(), "-2.5MB/s");
+
+    let parts = SizeFormatter::new(1024u32, BINARY).parts();
+    assert_eq!(parts.fraction().to_string(), "");
+    assert_eq!(parts.to_string(), "1 KiB");
+}
+
+#[test]
+fn separators() {
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .thousands_separator(Some(','))
+        .fixed_at(Some(FixedAt::Base));
+    let parts = SizeFormatter::new(12_345_678u32, OPTIONS).parts();
+    assert_eq!(parts.integer().to_string(), "12345678");
+    assert_eq!(parts.separator, Some(','));
+    assert_eq!(parts.separator_positions().collect::<Vec<_>>(), [2, 5]);
+    assert_eq!(parts.to_string(), "12,345,678 B");
+
+    let parts = SizeFormatter::new(123_456u32, OPTIONS).parts();
+    assert_eq!(parts.separator_positions().collect::<Vec<_>>(), [3]);
+    let parts = SizeFormatter::new(123u32, OPTIONS).parts();
+    assert_eq!(parts.separator_positions().count(), 0);
+    let parts = SizeFormatter::new(123_456u32, DECIMAL.fixed_at(Some(FixedAt::Base))).parts();
+    assert_eq!(parts.separator_positions().count(), 0);
+}
+
+#[test]
+fn bounds_and_notations() {
+    let parts = SizeFormatter::new(5u32, BINARY.min_unit(Some(FixedAt::Kilo))).parts();
+    assert_eq!(parts.bound, Some(Bound::LessThan));
+    assert_eq!(parts.integer().to_string(), "0");
+    assert_eq!(parts.fraction().to_string(), "01");
+    assert_eq!(parts.to_string(), "< 0.01 KiB");
+
+    let parts = ISizeFormatter::new(-5, BINARY.min_unit(Some(FixedAt::Kilo))).parts();
+    assert_eq!(parts.bound, Some(Bound::GreaterThan));
+    assert!(parts.negative);
+    assert_eq!(parts.to_string(), "> -0.01 KiB");
+
+    let parts = SizeFormatter::new(u128::MAX, BINARY.overflow(Overflow::Saturate)).parts();
+    assert_eq!(parts.bound, Some(Bound::GreaterThan));
+    assert_eq!(parts.integer().to_string(), "999");
+    assert_eq!(parts.to_string(), "> 999 YiB");
+
+    let parts = SizeFormatter::new(
+        1234 * 10u128.pow(24),
+        DECIMAL.overflow(Overflow::Scientific),
+    )
+    .parts();
+    assert_eq!(parts.integer().to_string(), "1");
+    assert_eq!(parts.fraction().to_string(), "23");
+    assert_eq!(parts.exponent, Some(3));
+    assert_eq!(parts.to_string(), "1.23e3 YB");
+
+    let parts = ISizeFormatter::new(f64::NEG_INFINITY, DECIMAL).parts();
+    assert!(parts.negative);
+    assert_eq!(parts.non_finite, Some(NonFinite::Infinity));
+    assert!(parts.integer().is_empty());
+    assert_eq!(parts.to_string(), "-inf B");
+
+    let parts = ISizeFormatter::new(f64::NAN, DECIMAL).parts();
+    assert!(!parts.negative);
+    assert_eq!(parts.non_finite, Some(NonFinite::NaN));
+    assert_eq!(parts.to_string(), "NaN B");
+
+    let parts = ISizeFormatter::new(f64::NAN, DECIMAL.placeholder(Some("n/a"))).parts();
+    assert_eq!(parts.placeholder, Some("n/a"));
+    assert_eq!(parts.integer().to_string(), "");
+    assert_eq!(parts.unit, "");
+    assert_eq!(parts.to_string(), "n/a");
+}
+
+#[test]
+fn long_numbers() {
+    // Every digit is kept, however long the number
+    let parts = ISizeFormatter::new(f64::MAX, BINARY.fixed_at(Some(FixedAt::Base))).parts();
+    assert_eq!(parts.integer().len(), 309);
+    assert_eq!(parts.fraction().len(), 0);
+    assert_eq!(parts.to_string(), format!("{:.0} B", f64::MAX));
+
+    let parts = ISizeFormatter::new(1.0 / 3.0, DECIMAL.decimal_places(38)).parts();
+    assert_eq!(parts.integer().to_string(), "0");
+    assert_eq!(parts.fraction().len(), 38);
+    assert!(parts.fraction().to_string().starts_with("3333"));
+
+    let parts = SizeFormatter::new(u128::MAX, DECIMAL.fixed_at(Some(FixedAt::Base))).parts();
+    assert_eq!(parts.integer().to_string(), u128::MAX.to_string());
+}
+
+#[test]
+fn display_matches_formatters() {
+    const OPTIONS: [FormatSizeOptions; 4] = [
+        BINARY,
+        DECIMAL
+            .thousands_separator(Some('\u{202f}'))
+            .fixed_at(Some(FixedAt::Kilo)),
+        DECIMAL.significant_digits(Some(3)).long_units(true),
+        BINARY
+            .min_fraction_digits(Some(0))
+            .overflow(Overflow::Engineering),
+    ];
+    for options in OPTIONS {
+        for value in [0, 1, 999, 1_000_999, 123_456_789_012, u64::MAX] {
+            let parts = SizeFormatter::new(value, options).parts();
+            assert_eq!(parts.to_string(), format_size(value, options));
+        }
+        for value in [-1.5e30, -0.25, 1e-300, 7.77e300] {
+            let parts = ISizeFormatter::new(value, options).parts();
+            assert_eq!(parts.to_string(), format_size_i(value, options));
+        }
+    }
+
+    let parts = SizeFormatter::new(1536u32, BINARY).parts();
+    assert_eq!(format!("{:>10}", parts), "  1.50 KiB");
+}
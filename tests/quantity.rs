@@ -0,0 +1,104 @@
+use humansize::{
+    format_quantity, FixedAt, FormatSizeOptions, Overflow, QuantityFormatter, UnitTable, BINARY,
+    DECIMAL,
+};
+
+#[test]
+fn large_prefixes() {
+    assert_eq!(format_quantity(0u32, "Hz", DECIMAL), "0 Hz");
+    assert_eq!(format_quantity(999u32, "Hz", DECIMAL), "999 Hz");
+    assert_eq!(format_quantity(2_400_000_000u64, "Hz", DECIMAL), "2.40 GHz");
+    assert_eq!(
+        format_quantity(1500u32, "requests", DECIMAL),
+        "1.50 krequests"
+    );
+    assert_eq!(format_quantity(-1_500_000i32, "W", DECIMAL), "-1.50 MW");
+
+    // The kilo is always 1000, and the unit labels options are ignored
+    assert_eq!(
+        format_quantity(1024u32, "J", BINARY.long_units(true)),
+        "1.02 kJ"
+    );
+    assert_eq!(
+        format_quantity(
+            1500u32,
+            "Hz",
+            DECIMAL.unit_table(Some(UnitTable::new(&["a"], false)))
+        ),
+        "1.50 kHz"
+    );
+    assert_eq!(
+        format_quantity(10u128.pow(30), "W", DECIMAL.extended_prefixes(true)),
+        "1 QW"
+    );
+}
+
+#[test]
+fn small_prefixes() {
+    assert_eq!(format_quantity(0.5, "W", DECIMAL), "500 mW");
+    assert_eq!(format_quantity(0.0015, "W", DECIMAL), "1.50 mW");
+    assert_eq!(format_quantity(-0.000_002_5, "A", DECIMAL), "-2.50 µA");
+    assert_eq!(format_quantity(3e-9, "s", DECIMAL), "3 ns");
+    assert_eq!(format_quantity(4.2e-12, "F", DECIMAL), "4.20 pF");
+    assert_eq!(format_quantity(4.2e-15, "F", DECIMAL), "0 pF");
+
+    // Values which round to 1 keep the base unit
+    assert_eq!(format_quantity(0.999, "V", DECIMAL), "1 V");
+    assert_eq!(
+        format_quantity(0.999, "V", DECIMAL.decimal_places(3)),
+        "999 mV"
+    );
+
+    // Forcing a unit disables the sub-unit prefixes
+    assert_eq!(
+        format_quantity(0.5, "W", DECIMAL.fixed_at(Some(FixedAt::Base))),
+        "0.50 W"
+    );
+}
+
+#[test]
+fn quantity_options() {
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .space_after_value(false)
+        .suffix("/s")
+        .thousands_separator(Some(','))
+        .fixed_at(Some(FixedAt::Kilo));
+    assert_eq!(
+        format_quantity(12_345_678u32, "req", OPTIONS),
+        "12,345.68kreq/s"
+    );
+    assert_eq!(
+        format_quantity(
+            1234 * 10u128.pow(24),
+            "W",
+            DECIMAL.overflow(Overflow::Scientific)
+        ),
+        "1.23e3 YW"
+    );
+    assert_eq!(
+        format_quantity(f64::NAN, "W", DECIMAL.placeholder(Some("n/a"))),
+        "n/a"
+    );
+    assert_eq!(
+        format_quantity(0.0015, "W", DECIMAL.min_fraction_digits(Some(0))),
+        "1.5 mW"
+    );
+    assert_eq!(
+        format_quantity(2_000_000u32, "W", DECIMAL.min_fraction_digits(Some(1))),
+        "2.0 MW"
+    );
+    assert_eq!(
+        format_quantity(0.5, "W", DECIMAL.min_unit(Some(FixedAt::Base))),
+        "0.50 W"
+    );
+    assert_eq!(
+        format_quantity(4u32, "Hz", DECIMAL.min_unit(Some(FixedAt::Kilo))),
+        "< 0.01 kHz"
+    );
+
+    let formatter = QuantityFormatter::new(1536u32, "Hz", DECIMAL);
+    assert_eq!(format!("{:>10}", formatter), "  1.54 kHz");
+    assert_eq!(format!("{:.1}", formatter), "1.5 kHz");
+    assert!(QuantityFormatter::try_new(f64::INFINITY, "Hz", DECIMAL).is_err());
+    assert!(QuantityFormatter::try_new(1.5, "Hz", DECIMAL).is_ok());
+}
@@ -0,0 +1,283 @@
+#![cfg(feature = "serde")]
+
+use humansize::{presets, ByteSize, ByteSizeWith};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Config {
+    #[serde(with = "humansize::serde::binary")]
+    max_upload: u64,
+    #[serde(with = "humansize::serde::decimal")]
+    disk: u128,
+    #[serde(with = "humansize::serde::windows")]
+    memory: u32,
+    cache: ByteSize,
+}
+
+#[test]
+fn serialize_sizes() {
+    let config = Config {
+        max_upload: 512 * 1024 * 1024,
+        disk: 2_000_000_000_000,
+        memory: 1024,
+        cache: ByteSize::mib(64),
+    };
+
+    assert_eq!(
+        serde_json::to_string(&config).unwrap(),
+        r#"{"max_upload":"512 MiB","disk":"2 TB","memory":"1 kB","cache":"64 MiB"}"#
+    );
+}
+
+#[test]
+fn deserialize_sizes() {
+    let config: Config = serde_json::from_str(
+        r#"{"max_upload":"512 MiB","disk":2000000000000,"memory":"1 kB","cache":"64 MiB"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        config,
+        Config {
+            max_upload: 512 * 1024 * 1024,
+            disk: 2_000_000_000_000,
+            memory: 1024,
+            cache: ByteSize::mib(64),
+        }
+    );
+}
+
+#[test]
+fn round_trip() {
+    let config = Config {
+        max_upload: 1_500_000,
+        disk: u128::MAX,
+        memory: 1025,
+        cache: ByteSize::b(1_048_577),
+    };
+
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        json,
+        r#"{"max_upload":1500000,"disk":"340282366920938463463374607431768211455","memory":1025,"cache":1048577}"#
+    );
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+    // Sizes the formatted string holds exactly are still written as strings
+    let config = Config {
+        max_upload: 1536,
+        disk: 2_500_000,
+        memory: 1024,
+        cache: ByteSize::kib(1536),
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(
+        json,
+        r#"{"max_upload":"1.50 KiB","disk":"2.50 MB","memory":"1 kB","cache":"1.50 MiB"}"#
+    );
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+}
+
+#[test]
+fn byte_size_with_options() {
+    let size: ByteSizeWith<presets::Decimal> = ByteSize::mb(3).with_options();
+    assert_eq!(serde_json::to_string(&size).unwrap(), r#""3 MB""#);
+    assert_eq!(
+        serde_json::from_str::<ByteSizeWith<presets::Decimal>>(r#""3 MB""#).unwrap(),
+        size
+    );
+    assert_eq!(
+        serde_json::from_str::<ByteSizeWith<presets::Windows>>(r#""3 MB""#).unwrap(),
+        ByteSize::mib(3).with_options()
+    );
+}
+
+#[test]
+fn binary_formats() {
+    let config = Config {
+        max_upload: 512 * 1024 * 1024,
+        disk: u128::MAX,
+        memory: 1024,
+        cache: ByteSize::mib(64),
+    };
+
+    let bytes = bincode::serialize(&config).unwrap();
+    assert_eq!(bytes.len(), 8 + 16 + 8 + 8);
+    assert_eq!(bincode::deserialize::<Config>(&bytes).unwrap(), config);
+}
+
+#[test]
+fn deserialize_errors() {
+    let err = serde_json::from_str::<Config>(
+        r#"{"max_upload":"512 parsecs","disk":0,"memory":0,"cache":0}"#,
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with(r#"invalid size "512 parsecs": unrecognized size unit"#));
+
+    let err = serde_json::from_str::<Config>(r#"{"max_upload":0,"disk":0,"memory":-1,"cache":0}"#)
+        .unwrap_err();
+    assert!(err.to_string().starts_with("invalid value: integer `-1`"));
+
+    let err =
+        serde_json::from_str::<Config>(r#"{"max_upload":0,"disk":0,"memory":"5 GB","cache":0}"#)
+            .unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with(r#"invalid size "5 GB": size is too large"#));
+}
+
+#[test]
+fn deserialize_options() {
+    use humansize::{format_size, FixedAt, FormatSizeOptions, BINARY};
+
+    let options: FormatSizeOptions = serde_json::from_str(r#""windows""#).unwrap();
+    assert_eq!(format_size(1024u32, options), "1 kB");
+    let options: FormatSizeOptions = serde_json::from_str(r#""jedec""#).unwrap();
+    assert_eq!(format_size(1024u32, options), "1 KB");
+
+    let options: FormatSizeOptions = serde_json::from_str(
+        r#"{ "kilo": { "custom": 512 }, "scale_ladder": [
+            { "multiplier": 1, "label": "B" },
+            { "multiplier": 1048576, "label": "blocks of 1 MiB" }
+        ] }"#,
+    )
+    .unwrap();
+    assert_eq!(format_size(3_145_728u32, options), "3 blocks of 1 MiB");
+    assert_eq!(options.kilo, humansize::Kilo::Custom(512));
+
+    let options: FormatSizeOptions = serde_json::from_str(
+        r#"{ "decimal_places": 1, "preset": "binary", "fixed_at": "kilo", "thousands_separator": "," }"#,
+    )
+    .unwrap();
+    assert_eq!(format_size(16_584_975u32, options), "16,196.3 KiB");
+
+    let options: FormatSizeOptions =
+        serde_json::from_str(r#"{ "long_units": true, "suffix": " per fortnight" }"#).unwrap();
+    assert_eq!(format_size(2000u32, options), "2 Kilobytes per fortnight");
+
+    let options: FormatSizeOptions =
+        serde_json::from_str(r#"{ "preset": "binary", "placeholder": "n/a" }"#).unwrap();
+    assert_eq!(format_size(u128::MAX, options), "n/a");
+
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .fixed_at(Some(FixedAt::Mega))
+        .suffix("/s");
+    let options: FormatSizeOptions =
+        serde_json::from_str(&serde_json::to_string(&OPTIONS).unwrap()).unwrap();
+    assert_eq!(
+        format_size(3_145_728u32, options),
+        format_size(3_145_728u32, OPTIONS)
+    );
+
+    let options: FormatSizeOptions = serde_json::from_str(
+        r#"{ "preset": "binary", "unit_table": "compact", "space_after_value": false }"#,
+    )
+    .unwrap();
+    assert_eq!(format_size(4096u32, options), "4K");
+
+    let options: FormatSizeOptions =
+        serde_json::from_str(r#"{ "unit_table": { "labels": ["o", "Ko", "Mo", "Go"] } }"#).unwrap();
+    assert_eq!(format_size(1_500_000u32, options), "1.50 Mo");
+    let json = serde_json::to_value(options).unwrap();
+    assert_eq!(
+        json["unit_table"],
+        serde_json::json!({ "labels": ["o", "Ko", "Mo", "Go"], "plural": false })
+    );
+    let json =
+        serde_json::to_value(humansize::DECIMAL.unit_table(Some(humansize::UnitTable::COMPACT)));
+    assert_eq!(json.unwrap()["unit_table"], "compact");
+    let json =
+        serde_json::to_value(humansize::DECIMAL.unit_table(Some(humansize::UnitTable::JEDEC_LONG)));
+    assert_eq!(json.unwrap()["unit_table"], "jedec_long");
+
+    let options: FormatSizeOptions =
+        serde_json::from_str(r#"{ "preset": "binary", "min_unit": "kilo", "max_unit": "giga" }"#)
+            .unwrap();
+    assert_eq!(format_size(5u32, options), "< 0.01 KiB");
+    assert_eq!(format_size(2_199_023_255_552u64, options), "2048 GiB");
+
+    let options: FormatSizeOptions =
+        serde_json::from_str(r#"{ "preset": "binary", "unit_decimal_places": [0, 0, 1, 2] }"#)
+            .unwrap();
+    assert_eq!(format_size(1536u32, options), "2 KiB");
+    assert_eq!(format_size(1_342_177_280u32, options), "1.25 GiB");
+
+    let options: FormatSizeOptions =
+        serde_json::from_str(r#"{ "preset": "binary", "min_fraction_digits": 0 }"#).unwrap();
+    assert_eq!(format_size(1536u32, options), "1.5 KiB");
+
+    let options: FormatSizeOptions = serde_json::from_str(
+        r#"{ "preset": "binary", "fixed_at": null, "min_fraction_digits": null }"#,
+    )
+    .unwrap();
+    assert_eq!(options.fixed_at, None);
+    assert_eq!(options.min_fraction_digits, None);
+
+    let err = serde_json::from_str::<FormatSizeOptions>(r#"{ "decimals": 1 }"#).unwrap_err();
+    assert!(err.to_string().starts_with("unknown field `decimals`"));
+
+    let err = serde_json::from_str::<FormatSizeOptions>(r#"{ "suffix": "/s", "suffix": "/h" }"#)
+        .unwrap_err();
+    assert!(err.to_string().starts_with("duplicate field `suffix`"));
+
+    let err = serde_json::from_str::<FormatSizeOptions>(r#""metric""#).unwrap_err();
+    assert!(err.to_string().starts_with("unknown variant `metric`"));
+
+    let err =
+        serde_json::from_str::<FormatSizeOptions>(r#"{ "kilo": "hexadecimal" }"#).unwrap_err();
+    assert!(err.to_string().starts_with("unknown variant `hexadecimal`"));
+
+    let err =
+        serde_json::from_str::<FormatSizeOptions>(r#"{ "decimal_places": 500 }"#).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("decimal_places must be at most 38"));
+}
+
+#[test]
+fn serialize_parts() {
+    use humansize::{FixedAt, SizeFormatter, DECIMAL};
+
+    let options = DECIMAL
+        .thousands_separator(Some(','))
+        .fixed_at(Some(FixedAt::Kilo));
+    let parts = SizeFormatter::new(1_234_567_890u64, options).parts();
+    assert_eq!(
+        serde_json::to_value(parts).unwrap(),
+        serde_json::json!({
+            "placeholder": null,
+            "bound": null,
+            "negative": false,
+            "non_finite": null,
+            "integer": "1234567",
+            "separator": ",",
+            "separator_positions": [1, 4],
+            "fraction": "89",
+            "exponent": null,
+            "space": true,
+            "unit": "kB",
+            "suffix": "",
+            "scale": 1,
+            "value": 1234567.89,
+        })
+    );
+
+    let parts = SizeFormatter::new(4u32, DECIMAL.min_unit(Some(FixedAt::Kilo))).parts();
+    assert_eq!(serde_json::to_value(parts).unwrap()["bound"], "less_than");
+}
+
+#[test]
+fn serialize_units() {
+    use humansize::Unit;
+
+    assert_eq!(
+        serde_json::to_string(&Unit::Kibibyte).unwrap(),
+        r#""kibibyte""#
+    );
+    assert_eq!(
+        serde_json::from_str::<Unit>(r#""gigabit""#).unwrap(),
+        Unit::Gigabit
+    );
+}
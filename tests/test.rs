@@ -1,14 +1,20 @@
+// Options are borrowed on purpose to exercise the `AsRef<FormatSizeOptions>` bound
+#![allow(clippy::needless_borrows_for_generic_args)]
+
 use humansize::{
-    format_size, format_size_i, BaseUnit, FixedAt, FormatSizeOptions, BINARY, DECIMAL, WINDOWS,
+    format_size, format_size_i, try_format_size, try_format_size_i, BaseUnit, FixedAt,
+    FormatSizeError, FormatSizeOptions, FormatSizeOptionsBuilder, ISizeFormatter, Kilo,
+    OptionsError, Overflow, ScaleStep, SizeFormatter, SizeScale, ToF64, UnitTable, BINARY, DECIMAL,
+    JEDEC, WINDOWS,
 };
 
 #[test]
 fn test_sizes() {
     assert_eq!(format_size(0u32, BINARY), "0 B");
     assert_eq!(format_size(999u32, BINARY), "999 B");
-    assert_eq!(format_size(1000u32, BINARY), "1000 B");
+    assert_eq!(format_size(1000u32, BINARY), "0.98 KiB");
     assert_eq!(format_size(1000u32, DECIMAL), "1 kB");
-    assert_eq!(format_size(1023u32, BINARY), "1023 B");
+    assert_eq!(format_size(1023u32, BINARY), "1 KiB");
     assert_eq!(format_size(1023u32, DECIMAL), "1.02 kB");
     assert_eq!(format_size(1024u32, BINARY), "1 KiB");
     assert_eq!(format_size(1024u32, WINDOWS), "1 kB");
@@ -114,7 +120,7 @@ fn max_value_decimal() {
     const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
         .decimal_places(7)
         .long_units(true);
-    assert_eq!(format_size(core::u64::MAX, &OPTIONS), "18.4467441 Exabytes",);
+    assert_eq!(format_size(u64::MAX, &OPTIONS), "18.4467441 Exabytes",);
 }
 
 #[test]
@@ -123,7 +129,7 @@ fn max_value_binary() {
         .decimal_places(7)
         .long_units(true);
 
-    assert_eq!(format_size(core::u64::MAX, &OPTIONS), "16 Exbibytes",);
+    assert_eq!(format_size(u64::MAX, &OPTIONS), "16 Exbibytes",);
 }
 
 #[test]
@@ -133,7 +139,7 @@ fn max_value_binary_128() {
         .long_units(true);
 
     assert_eq!(
-        format_size(core::u128::MAX, &OPTIONS),
+        format_size(u128::MAX, &OPTIONS),
         "281474976710656 Yobibytes",
     );
 }
@@ -200,10 +206,947 @@ fn padding() {
     assert_eq!(formatted, " 1 kB");
 }
 
-// #[test]
-// fn padding_no_alloc() {
-//     let res_no_alloc = SizeFormatter::new(1000u32, DECIMAL);
-//     let formatted_no_alloc = format!("{:>5}", res_no_alloc);
+#[test]
+fn padding_no_alloc() {
+    let res_no_alloc = SizeFormatter::new(1000u32, DECIMAL);
+    let formatted_no_alloc = format!("{:>5}", res_no_alloc);
+
+    assert_eq!(formatted_no_alloc, " 1 kB");
+}
+
+#[test]
+fn formatter_flags() {
+    let formatter = SizeFormatter::new(1536u32, BINARY);
+
+    assert_eq!(format!("{:10}", formatter), "  1.50 KiB");
+    assert_eq!(format!("{:<10}|", formatter), "1.50 KiB  |");
+    assert_eq!(format!("{:*^12}", formatter), "**1.50 KiB**");
+    assert_eq!(format!("{:4}", formatter), "1.50 KiB");
+    assert_eq!(format!("{:.3}", formatter), "1.500 KiB");
+    assert_eq!(format!("{:.0}", formatter), "2 KiB");
+    assert_eq!(format!("{:#}", formatter), "1.50 Kibibyte");
+    assert_eq!(format!("{:>#16.1}", formatter), "    1.5 Kibibyte");
+    assert_eq!(
+        format!("{:>8}", ISizeFormatter::new(-1024, BINARY)),
+        "  -1 KiB"
+    );
+}
+
+#[test]
+fn significant_digits() {
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(BINARY).significant_digits(Some(3));
+
+    assert_eq!(format_size(1_289_748u32, OPTIONS), "1.23 MiB");
+    assert_eq!(format_size(12_897_484u32, OPTIONS), "12.3 MiB");
+    assert_eq!(format_size(128_974_848u32, OPTIONS), "123 MiB");
+    assert_eq!(format_size(999u32, OPTIONS), "999 B");
+    assert_eq!(format_size(1023u32, OPTIONS), "0.999 KiB");
+    assert_eq!(format_size(1024u32, OPTIONS), "1.00 KiB");
+    assert_eq!(format_size(10_475u32, OPTIONS), "10.2 KiB");
+    assert_eq!(format_size(10_236u32, OPTIONS), "10.0 KiB");
+    assert_eq!(format_size(1_048_575u32, OPTIONS), "1.00 MiB");
+    assert_eq!(format_size_i(-1_289_748, OPTIONS), "-1.23 MiB");
+    assert_eq!(format_size(5u32, OPTIONS), "5 B");
+    assert_eq!(format_size_i(5.5, OPTIONS), "5.50 B");
+    assert_eq!(format_size_i(10_236.0, OPTIONS), "10.0 KiB");
+
+    const FIXED: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .significant_digits(Some(2))
+        .fixed_at(Some(FixedAt::Mega))
+        .thousands_separator(Some(','));
+    assert_eq!(format_size(12_345u32, FIXED), "0.012 MB");
+    assert_eq!(format_size(1_234_467_890u32, FIXED), "1,234 MB");
+}
+
+#[test]
+fn rounding_modes() {
+    use humansize::Rounding;
+
+    const OPTIONS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL);
+    assert_eq!(
+        format_size(999_999_999u32, OPTIONS.fixed_at(Some(FixedAt::Giga))),
+        "1 GB"
+    );
+    assert_eq!(
+        format_size(
+            999_999_999u32,
+            OPTIONS
+                .fixed_at(Some(FixedAt::Giga))
+                .rounding(Rounding::Floor)
+        ),
+        "0.99 GB"
+    );
+    assert_eq!(
+        format_size(1_001_000u32, OPTIONS.rounding(Rounding::Ceil)),
+        "1.01 MB"
+    );
+    assert_eq!(
+        format_size(1_019_000u32, OPTIONS.rounding(Rounding::Truncate)),
+        "1.01 MB"
+    );
+    assert_eq!(format_size(1_125u32, OPTIONS), "1.12 kB");
+    assert_eq!(
+        format_size(1_125u32, OPTIONS.rounding(Rounding::HalfUp)),
+        "1.13 kB"
+    );
+    assert_eq!(format_size(2_500u32, OPTIONS.decimal_places(0)), "2 kB");
+    assert_eq!(
+        format_size(1_135u32, OPTIONS.rounding(Rounding::HalfEven)),
+        "1.14 kB"
+    );
+    assert_eq!(
+        format_size_i(-1_001_000, OPTIONS.rounding(Rounding::Floor)),
+        "-1.01 MB"
+    );
+
+    const SEPARATED: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .fixed_at(Some(FixedAt::Kilo))
+        .thousands_separator(Some(','))
+        .decimal_places(1);
+    assert_eq!(format_size(1_234_567u32, SEPARATED), "1,234.6 kB");
+    assert_eq!(
+        format_size(1_234_567u32, SEPARATED.rounding(Rounding::Floor)),
+        "1,234.5 kB"
+    );
+    assert_eq!(
+        format_size(1_234_999u32, SEPARATED.decimal_places(0)),
+        "1,235 kB"
+    );
+    assert_eq!(
+        format_size(1_234_999u32, SEPARATED.rounding(Rounding::Ceil)),
+        "1,235 kB"
+    );
+    assert_eq!(format_size_i(-1_234_567, SEPARATED), "-1,234.6 kB");
+}
+
+#[test]
+fn rollover() {
+    assert_eq!(format_size(999_999u32, DECIMAL), "1 MB");
+    assert_eq!(format_size(999_994u32, DECIMAL), "999.99 kB");
+    assert_eq!(format_size(1_048_575u32, BINARY), "1 MiB");
+    assert_eq!(format_size_i(-999_999, DECIMAL), "-1 MB");
 
-//     assert_eq!(formatted_no_alloc, " 1 kB");
-// }
+    // Never more than three integer digits, even with binary units
+    assert_eq!(format_size(999u32, BINARY), "999 B");
+    assert_eq!(format_size(1000u32, BINARY), "0.98 KiB");
+    assert_eq!(format_size(1023u32, BINARY), "1 KiB");
+    assert_eq!(format_size(1_023_487u32, BINARY), "999.50 KiB");
+    assert_eq!(format_size(1_023_996u32, BINARY), "0.98 MiB");
+
+    const KILO: FormatSizeOptions = FormatSizeOptions::from(BINARY).rollover_threshold(Some(1024));
+    assert_eq!(format_size(1023u32, KILO), "1023 B");
+    assert_eq!(format_size(1024u32, KILO), "1 KiB");
+
+    const FIXED: FormatSizeOptions = BINARY.fixed_at(Some(FixedAt::Base));
+    assert_eq!(format_size(1023u32, FIXED), "1023 B");
+}
+
+#[test]
+fn exact_integers() {
+    const BASE: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .fixed_at(Some(FixedAt::Base))
+        .thousands_separator(Some(','));
+    assert_eq!(format_size(u64::MAX, BASE), "18,446,744,073,709,551,615 B");
+    assert_eq!(
+        format_size(u128::MAX, BASE),
+        "340,282,366,920,938,463,463,374,607,431,768,211,455 B"
+    );
+    assert_eq!(
+        format_size_i(i128::MIN, BASE),
+        "-170,141,183,460,469,231,731,687,303,715,884,105,728 B"
+    );
+    assert_eq!(
+        format_size(9_007_199_254_740_993u64, BASE.thousands_separator(None)),
+        "9007199254740993 B"
+    );
+
+    const DECIMALS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL).decimal_places(7);
+    assert_eq!(
+        format_size(u128::MAX, DECIMALS),
+        "340282366920938.4634634 YB"
+    );
+    assert_eq!(
+        format_size(u64::MAX, DECIMALS.fixed_at(Some(FixedAt::Kilo))),
+        "18446744073709551.6150000 kB"
+    );
+    assert_eq!(
+        format_size(u64::MAX, BINARY.decimal_places(30)),
+        "15.999999999999999999132638262012 EiB"
+    );
+}
+
+#[test]
+fn separator_edge_cases() {
+    const BASE: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .fixed_at(Some(FixedAt::Base))
+        .decimal_places(0)
+        .thousands_separator(Some(','));
+    let huge = format_size_i(1e300, BASE);
+    assert!(huge.starts_with("1,000,000,000,000,000,052,504,760,255"));
+    assert!(huge.ends_with(",540,160 B"));
+    assert_eq!(huge.len(), 1 + 100 * 4 + 2);
+
+    const SEPARATED: FormatSizeOptions =
+        FormatSizeOptions::from(BINARY).thousands_separator(Some(','));
+    assert_eq!(format_size_i(f64::NAN, SEPARATED), "NaN B");
+    assert_eq!(format_size_i(f64::INFINITY, SEPARATED), "inf B");
+    assert_eq!(format_size_i(f64::NEG_INFINITY, SEPARATED), "-inf B");
+    assert_eq!(format_size_i(f64::NAN, BINARY), "NaN B");
+    assert_eq!(format_size_i(f64::INFINITY, DECIMAL), "inf B");
+
+    assert_eq!(
+        format_size(1234567u64, BASE.thousands_separator(Some('\u{202F}'))),
+        "1\u{202F}234\u{202F}567 B"
+    );
+    assert_eq!(
+        format_size_i(
+            -1234567.5,
+            BASE.decimal_places(1).thousands_separator(Some('’'))
+        ),
+        "-1’234’567.5 B"
+    );
+}
+
+#[test]
+fn try_format() {
+    assert_eq!(
+        try_format_size(1536u32, BINARY),
+        Ok(String::from("1.50 KiB"))
+    );
+    assert_eq!(
+        try_format_size_i(-1.5f64, DECIMAL),
+        Ok(String::from("-1.50 B"))
+    );
+    assert_eq!(
+        try_format_size_i(f64::NAN, BINARY),
+        Err(FormatSizeError::NonFinite)
+    );
+    assert_eq!(
+        try_format_size_i(f64::NEG_INFINITY, BINARY),
+        Err(FormatSizeError::NonFinite)
+    );
+    assert_eq!(
+        try_format_size(u128::MAX, DECIMAL),
+        Err(FormatSizeError::OutOfRange)
+    );
+    assert_eq!(
+        try_format_size_i(1e30, DECIMAL),
+        Err(FormatSizeError::OutOfRange)
+    );
+    assert_eq!(
+        try_format_size(u128::MAX, DECIMAL.fixed_at(Some(FixedAt::Yotta))),
+        Ok(String::from("340282366920938.46 YB"))
+    );
+    assert_eq!(
+        try_format_size(1u32, DECIMAL.thousands_separator(Some('.'))),
+        Err(FormatSizeError::InvalidOptions(
+            OptionsError::AmbiguousSeparator
+        ))
+    );
+    assert_eq!(
+        try_format_size(1u32, DECIMAL.significant_digits(Some(0))),
+        Err(FormatSizeError::InvalidOptions(
+            OptionsError::InvalidSignificantDigits
+        ))
+    );
+
+    const DASHBOARD: FormatSizeOptions = FormatSizeOptions::from(BINARY).placeholder(Some("n/a"));
+    assert_eq!(
+        try_format_size_i(f64::NAN, DASHBOARD),
+        Ok(String::from("n/a"))
+    );
+    assert_eq!(try_format_size_i(1e30, DASHBOARD), Ok(String::from("n/a")));
+    assert_eq!(
+        try_format_size(1024u32, DASHBOARD),
+        Ok(String::from("1 KiB"))
+    );
+    assert_eq!(format_size_i(f64::INFINITY, DASHBOARD), "n/a");
+    assert_eq!(
+        format!(
+            "{:>5}",
+            ISizeFormatter::new(f64::NAN, DASHBOARD.placeholder(Some("—")))
+        ),
+        "    —"
+    );
+
+    assert!(SizeFormatter::try_new(1u8, BINARY).is_ok());
+    assert_eq!(
+        ISizeFormatter::try_new(f32::NAN, BINARY).err(),
+        Some(FormatSizeError::NonFinite)
+    );
+}
+
+#[test]
+fn validate_options() {
+    assert_eq!(BINARY.validate(), Ok(()));
+    assert_eq!(DECIMAL.validate(), Ok(()));
+    assert_eq!(WINDOWS.validate(), Ok(()));
+    assert_eq!(
+        BINARY.decimal_places(500).validate(),
+        Err(OptionsError::TooManyDecimalPlaces)
+    );
+    assert_eq!(
+        BINARY.decimal_zeroes(39).validate(),
+        Err(OptionsError::TooManyDecimalZeroes)
+    );
+    assert_eq!(
+        BINARY.thousands_separator(Some('5')).validate(),
+        Err(OptionsError::AmbiguousSeparator)
+    );
+    assert_eq!(BINARY.thousands_separator(Some(' ')).validate(), Ok(()));
+    assert_eq!(
+        BINARY.significant_digits(Some(40)).validate(),
+        Err(OptionsError::InvalidSignificantDigits)
+    );
+    assert_eq!(
+        BINARY.rollover_threshold(Some(0)).validate(),
+        Err(OptionsError::ZeroRolloverThreshold)
+    );
+
+    const BUILT: Result<FormatSizeOptions, OptionsError> = FormatSizeOptionsBuilder::new(DECIMAL)
+        .fixed_at(Some(FixedAt::Kilo))
+        .thousands_separator(Some(','))
+        .build();
+    assert_eq!(format_size(1_234_567u32, BUILT.unwrap()), "1,234.57 kB");
+    assert_eq!(
+        FormatSizeOptionsBuilder::from(BINARY)
+            .decimal_places(39)
+            .build()
+            .err(),
+        Some(OptionsError::TooManyDecimalPlaces)
+    );
+    assert_eq!(
+        try_format_size(1u32, BINARY.decimal_places(39))
+            .unwrap_err()
+            .to_string(),
+        "invalid formatting options: decimal_places must be at most 38"
+    );
+}
+
+#[test]
+fn extended_prefixes() {
+    assert_eq!(format_size(u128::MAX, DECIMAL), "340282366920938.46 YB");
+    assert_eq!(
+        format_size(u128::MAX, DECIMAL.extended_prefixes(true)),
+        "340282366.92 QB"
+    );
+    assert_eq!(
+        format_size(u128::MAX / 1_000_000, DECIMAL.extended_prefixes(true)),
+        "340.28 QB"
+    );
+    assert_eq!(
+        format_size(10u128.pow(27), DECIMAL.extended_prefixes(true)),
+        "1 RB"
+    );
+    assert_eq!(
+        format_size(
+            1u128 << 100,
+            BINARY.extended_prefixes(true).long_units(true)
+        ),
+        "1 Quebibyte"
+    );
+    assert_eq!(
+        format_size(
+            1u128 << 90,
+            BINARY.base_unit(BaseUnit::Bit).extended_prefixes(true)
+        ),
+        "1 Ribit"
+    );
+    assert_eq!(
+        format_size(10u128.pow(27), DECIMAL.fixed_at(Some(FixedAt::Ronna))),
+        "1 RB"
+    );
+    assert_eq!(
+        try_format_size(u128::MAX / 1_000_000, DECIMAL.extended_prefixes(true)),
+        Ok(String::from("340.28 QB"))
+    );
+    assert_eq!(
+        try_format_size(u128::MAX, DECIMAL.extended_prefixes(true)),
+        Err(FormatSizeError::OutOfRange)
+    );
+    assert_eq!(
+        try_format_size_i(1e33, DECIMAL.extended_prefixes(true)),
+        Err(FormatSizeError::OutOfRange)
+    );
+}
+
+#[test]
+fn overflow_policies() {
+    const YB: u128 = 1_000_000_000_000_000_000_000_000;
+    assert_eq!(format_size(1234 * YB, DECIMAL), "1234 YB");
+    assert_eq!(
+        format_size(1234 * YB, DECIMAL.overflow(Overflow::Scientific)),
+        "1.23e3 YB"
+    );
+    assert_eq!(
+        format_size(12_345 * YB, DECIMAL.overflow(Overflow::Engineering)),
+        "12.35e3 YB"
+    );
+    assert_eq!(
+        format_size(9_999 * YB, DECIMAL.overflow(Overflow::Scientific)),
+        "1e4 YB"
+    );
+    assert_eq!(
+        format_size_i(-1e30, DECIMAL.overflow(Overflow::Engineering)),
+        "-1e6 YB"
+    );
+    assert_eq!(
+        format_size(1234 * YB, DECIMAL.overflow(Overflow::Saturate)),
+        "> 999 YB"
+    );
+    assert_eq!(
+        format_size(
+            u128::MAX,
+            BINARY
+                .overflow(Overflow::Saturate)
+                .thousands_separator(Some(','))
+        ),
+        "> 999 YiB"
+    );
+    assert_eq!(
+        format_size_i(-1e30, DECIMAL.overflow(Overflow::Saturate).long_units(true)),
+        "< -999 Yottabytes"
+    );
+    assert_eq!(
+        format_size(
+            12_345 * YB,
+            DECIMAL
+                .overflow(Overflow::Scientific)
+                .thousands_separator(Some(','))
+        ),
+        "1.23e4 YB"
+    );
+
+    // Values within the largest unit and fixed units are unaffected
+    assert_eq!(
+        format_size(999 * YB, DECIMAL.overflow(Overflow::Scientific)),
+        "999 YB"
+    );
+    assert_eq!(
+        format_size(
+            1234 * YB,
+            DECIMAL
+                .overflow(Overflow::Saturate)
+                .fixed_at(Some(FixedAt::Yotta))
+        ),
+        "1234 YB"
+    );
+    assert_eq!(
+        try_format_size(1234 * YB, DECIMAL.overflow(Overflow::Scientific)),
+        Ok(String::from("1.23e3 YB"))
+    );
+}
+
+#[test]
+fn jedec() {
+    assert_eq!(format_size(1024u32, JEDEC), "1 KB");
+    assert_eq!(format_size(1536u32, JEDEC), "1.50 KB");
+    assert_eq!(format_size(8u64 << 30, JEDEC), "8 GB");
+    assert_eq!(format_size(1u32, JEDEC.long_units(true)), "1 Byte");
+    assert_eq!(
+        format_size(2048u32, JEDEC.long_units(true)),
+        "2 Kilobytes (1024)"
+    );
+    assert_eq!(
+        format_size(1536u32, JEDEC.long_units(true)),
+        "1.50 Kilobyte (1024)"
+    );
+    assert_eq!(
+        format_size(3u64 << 30, JEDEC.long_units(true).base_unit(BaseUnit::Bit)),
+        "3 Gigabits (1024³)"
+    );
+    assert_eq!(
+        format_size(1u32, JEDEC.long_units(true).base_unit(BaseUnit::Bit)),
+        "1 Bit"
+    );
+    assert_eq!(
+        format_size(1024u32, JEDEC.base_unit(BaseUnit::Bit)),
+        "1 Kbit"
+    );
+    assert_eq!(format_size(1024u32, WINDOWS), "1 kB");
+}
+
+#[test]
+fn custom_scales() {
+    const BLOCKS: FormatSizeOptions = FormatSizeOptions::from(DECIMAL).kilo(Kilo::Custom(512));
+    assert_eq!(format_size(256u32, BLOCKS), "256 B");
+    assert_eq!(format_size(768u32, BLOCKS), "1.50 kB");
+    assert_eq!(format_size(512u32 * 512, BLOCKS), "1 MB");
+    assert_eq!(
+        BINARY.kilo(Kilo::Custom(1)).validate(),
+        Err(OptionsError::InvalidKilo)
+    );
+    // Invalid options are still displayed without panicking
+    assert_eq!(
+        format_size(2048u32, BINARY.kilo(Kilo::Custom(0))),
+        "2.05 KiB"
+    );
+
+    const LADDER: &[ScaleStep] = &[
+        ScaleStep::new(1, "B"),
+        ScaleStep::new(1_000_000, "MB"),
+        ScaleStep::new(1_000_000_000, "GB"),
+    ];
+    const SKIPPING: FormatSizeOptions = FormatSizeOptions::from(DECIMAL).scale_ladder(Some(LADDER));
+    assert_eq!(format_size(500_000u32, SKIPPING), "500000 B");
+    assert_eq!(format_size(999_999u32, SKIPPING), "999999 B");
+    assert_eq!(format_size(1_500_000u32, SKIPPING), "1.50 MB");
+    assert_eq!(format_size(999_999_999u32, SKIPPING), "1 GB");
+    assert_eq!(format_size(5_000_000_000_000u64, SKIPPING), "5000 GB");
+    assert_eq!(format_size_i(-2.5e6, SKIPPING), "-2.50 MB");
+    assert_eq!(
+        format_size(1_500_000u32, SKIPPING.fixed_at(Some(FixedAt::Base))),
+        "1500000 B"
+    );
+    assert_eq!(
+        try_format_size(5_000_000_000_000u64, SKIPPING),
+        Ok(String::from("5000 GB"))
+    );
+
+    const TRACKS: &[ScaleStep] = &[
+        ScaleStep::new(56_664, "tracks"),
+        ScaleStep::new(56_664 * 15, "cylinders"),
+    ];
+    assert_eq!(
+        format_size(56_664u32 * 3, DECIMAL.scale_ladder(Some(TRACKS))),
+        "3 tracks"
+    );
+    assert_eq!(
+        format_size(56_664u32 * 45, DECIMAL.scale_ladder(Some(TRACKS))),
+        "3 cylinders"
+    );
+
+    assert_eq!(
+        DECIMAL.scale_ladder(Some(&[])).validate(),
+        Err(OptionsError::InvalidLadder)
+    );
+    const NOT_INCREASING: &[ScaleStep] = &[ScaleStep::new(10, "a"), ScaleStep::new(10, "b")];
+    assert_eq!(
+        DECIMAL.scale_ladder(Some(NOT_INCREASING)).validate(),
+        Err(OptionsError::InvalidLadder)
+    );
+    assert_eq!(
+        SKIPPING.fixed_at(Some(FixedAt::Tera)).validate(),
+        Err(OptionsError::InvalidLadder)
+    );
+    assert_eq!(
+        format_size(1000u32, DECIMAL.scale_ladder(Some(&[]))),
+        "1 kB"
+    );
+}
+
+#[test]
+fn unit_tables() {
+    const LS: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .unit_table(Some(UnitTable::COMPACT))
+        .space_after_value(false)
+        .decimal_places(1);
+    assert_eq!(format_size(4096u32, LS), "4K");
+    assert_eq!(format_size(4200u32, LS), "4.1K");
+    assert_eq!(format_size(12_582_912u32, LS), "12M");
+    assert_eq!(format_size(1_395_864_371u32, LS), "1.3G");
+    assert_eq!(format_size(512u32, LS), "512");
+    assert_eq!(
+        format_size(512u32, BINARY.unit_table(Some(UnitTable::COMPACT))),
+        "512"
+    );
+    assert_eq!(
+        format_size(2048u32, BINARY.unit_table(Some(UnitTable::COMPACT))),
+        "2 K"
+    );
+
+    const OCTETS: UnitTable = UnitTable::new(&["o", "Ko", "Mo", "Go", "To"], false);
+    const FRENCH: FormatSizeOptions = FormatSizeOptions::from(DECIMAL).unit_table(Some(OCTETS));
+    assert_eq!(format_size(1_500_000u32, FRENCH), "1.50 Mo");
+    // Scaling stops at the last label of the table
+    assert_eq!(format_size(5_000_000_000_000_000u64, FRENCH), "5000 To");
+    assert_eq!(
+        try_format_size(5_000_000_000_000_000u64, FRENCH),
+        Err(FormatSizeError::OutOfRange)
+    );
+
+    const PAGES: UnitTable = UnitTable::new(&["pages", "kilopages"], true);
+    const PLURAL: FormatSizeOptions = FormatSizeOptions::from(DECIMAL).unit_table(Some(PAGES));
+    assert_eq!(format_size(1u32, PLURAL), "1 page");
+    assert_eq!(format_size(12u32, PLURAL), "12 pages");
+    assert_eq!(format_size(1000u32, PLURAL), "1 kilopage");
+
+    // The built-in tables match the labels selected by the other options
+    assert_eq!(
+        format_size(
+            1_500_000u32,
+            DECIMAL.unit_table(Some(UnitTable::BINARY_LONG))
+        ),
+        "1.50 Mebibyte"
+    );
+    assert_eq!(
+        format_size(1_048_576u32, BINARY.unit_table(Some(UnitTable::JEDEC_LONG))),
+        format_size(1_048_576u32, JEDEC.long_units(true))
+    );
+
+    assert_eq!(
+        FRENCH.fixed_at(Some(FixedAt::Peta)).validate(),
+        Err(OptionsError::InvalidUnitTable)
+    );
+    assert_eq!(
+        DECIMAL
+            .unit_table(Some(UnitTable::new(&[], false)))
+            .validate(),
+        Err(OptionsError::InvalidUnitTable)
+    );
+    // Invalid tables fall back to the standard labels
+    assert_eq!(
+        format_size(
+            1000u32,
+            DECIMAL.unit_table(Some(UnitTable::new(&[], false)))
+        ),
+        "1 kB"
+    );
+}
+
+#[test]
+fn unit_bounds() {
+    const LISTING: FormatSizeOptions =
+        FormatSizeOptions::from(BINARY).min_unit(Some(FixedAt::Kilo));
+    assert_eq!(format_size(0u32, LISTING), "0 KiB");
+    assert_eq!(format_size(5u32, LISTING), "< 0.01 KiB");
+    assert_eq!(format_size(512u32, LISTING), "0.50 KiB");
+    assert_eq!(format_size(1536u32, LISTING), "1.50 KiB");
+    assert_eq!(format_size(3_145_728u32, LISTING), "3 MiB");
+    assert_eq!(format_size_i(-5, LISTING), "> -0.01 KiB");
+    assert_eq!(format_size_i(0.001, LISTING), "< 0.01 KiB");
+    assert_eq!(format_size(5u32, LISTING.decimal_places(0)), "< 1 KiB");
+    assert_eq!(
+        format_size(5u32, LISTING.decimal_places(0).long_units(true)),
+        "< 1 Kibibyte"
+    );
+    assert_eq!(format_size(5u32, LISTING.decimal_places(3)), "0.005 KiB");
+    // Rounding up never displays zero
+    assert_eq!(
+        format_size(5u32, LISTING.rounding(humansize::Rounding::Ceil)),
+        "0.01 KiB"
+    );
+
+    const RAM: FormatSizeOptions = FormatSizeOptions::from(BINARY).max_unit(Some(FixedAt::Giga));
+    assert_eq!(format_size(1536u32, RAM), "1.50 KiB");
+    assert_eq!(format_size(8_589_934_592u64, RAM), "8 GiB");
+    assert_eq!(format_size(2_199_023_255_552u64, RAM), "2048 GiB");
+    // Staying in the largest allowed unit isn't an overflow
+    assert_eq!(
+        try_format_size(2_199_023_255_552u64, RAM.overflow(Overflow::Saturate)),
+        Ok(String::from("2048 GiB"))
+    );
+
+    const BOTH: FormatSizeOptions = FormatSizeOptions::from(DECIMAL)
+        .min_unit(Some(FixedAt::Mega))
+        .max_unit(Some(FixedAt::Mega));
+    assert_eq!(format_size(1_500_000_000u32, BOTH), "1500 MB");
+    assert_eq!(format_size(1000u32, BOTH), "< 0.01 MB");
+
+    // `fixed_at` takes precedence
+    assert_eq!(
+        format_size(5u32, LISTING.fixed_at(Some(FixedAt::Base))),
+        "5 B"
+    );
+    assert_eq!(
+        format_size(5u32, LISTING.fixed_at(Some(FixedAt::Kilo))),
+        "0 KiB"
+    );
+
+    assert_eq!(
+        DECIMAL
+            .min_unit(Some(FixedAt::Giga))
+            .max_unit(Some(FixedAt::Mega))
+            .validate(),
+        Err(OptionsError::InvalidUnitRange)
+    );
+}
+
+#[test]
+fn unit_decimal_places() {
+    const PLACES: FormatSizeOptions =
+        FormatSizeOptions::from(BINARY).unit_decimal_places(Some(&[0, 0, 1, 2]));
+    assert_eq!(format_size(512u32, PLACES), "512 B");
+    assert_eq!(format_size(1536u32, PLACES), "2 KiB");
+    assert_eq!(format_size(1_572_864u32, PLACES), "1.5 MiB");
+    assert_eq!(format_size(1_342_177_280u32, PLACES), "1.25 GiB");
+    // The last entry applies to the larger units
+    assert_eq!(format_size(1_374_389_534_720u64, PLACES), "1.25 TiB");
+    assert_eq!(format_size_i(-1_572_864, PLACES), "-1.5 MiB");
+    assert_eq!(format_size_i(1_572_864.0, PLACES), "1.5 MiB");
+    // Rounding in a unit without decimals still moves to the next one
+    assert_eq!(format_size(1_048_500u32, PLACES), "1 MiB");
+    assert_eq!(format_size(2u32 << 20, PLACES.decimal_zeroes(1)), "2.0 MiB");
+
+    // The precision flag overrides every unit
+    let formatter = SizeFormatter::new(1_342_177_280u32, PLACES);
+    assert_eq!(format!("{:.3}", formatter), "1.250 GiB");
+    assert_eq!(
+        format_size(1_342_177_280u32, PLACES.significant_digits(Some(1))),
+        "1 GiB"
+    );
+
+    // The per-unit places also apply to the mantissa of values beyond the largest unit
+    assert_eq!(
+        format_size(
+            u128::MAX,
+            DECIMAL
+                .unit_decimal_places(Some(&[0]))
+                .overflow(Overflow::Scientific)
+        ),
+        "3e14 YB"
+    );
+    assert_eq!(
+        format_size(
+            u128::MAX,
+            DECIMAL
+                .unit_decimal_places(Some(&[0, 1]))
+                .overflow(Overflow::Engineering)
+        ),
+        "340.3e12 YB"
+    );
+
+    assert_eq!(
+        DECIMAL.unit_decimal_places(Some(&[0, 39])).validate(),
+        Err(OptionsError::TooManyDecimalPlaces)
+    );
+    assert_eq!(
+        format_size(1500u32, DECIMAL.unit_decimal_places(Some(&[]))),
+        "1.50 kB"
+    );
+}
+
+#[test]
+fn min_fraction_digits() {
+    const TRIMMED: FormatSizeOptions = FormatSizeOptions::from(BINARY).min_fraction_digits(Some(0));
+    assert_eq!(format_size(1536u32, TRIMMED), "1.5 KiB");
+    assert_eq!(format_size(1024u32, TRIMMED), "1 KiB");
+    assert_eq!(format_size(1280u32, TRIMMED), "1.25 KiB");
+    assert_eq!(format_size(1_030u32, TRIMMED), "1.01 KiB");
+    assert_eq!(format_size(1_029u32, TRIMMED), "1 KiB");
+    assert_eq!(format_size_i(-1536, TRIMMED), "-1.5 KiB");
+    assert_eq!(format_size_i(1536.0, TRIMMED), "1.5 KiB");
+    assert_eq!(format_size(0u32, TRIMMED), "0 B");
+
+    const ONE: FormatSizeOptions = FormatSizeOptions::from(BINARY).min_fraction_digits(Some(1));
+    assert_eq!(format_size(1024u32, ONE), "1.0 KiB");
+    assert_eq!(format_size(1536u32, ONE), "1.5 KiB");
+    assert_eq!(format_size(1280u32, ONE), "1.25 KiB");
+    assert_eq!(format_size(3u32 << 20, ONE), "3.0 MiB");
+    // Never more digits than `decimal_places`, which `validate` rejects
+    assert_eq!(format_size(1536u32, ONE.decimal_places(0)), "2 KiB");
+    assert_eq!(
+        format_size(1024u32, BINARY.min_fraction_digits(Some(3))),
+        "1.00 KiB"
+    );
+    assert_eq!(
+        BINARY.min_fraction_digits(Some(3)).validate(),
+        Err(OptionsError::InvalidFractionDigits)
+    );
+    assert_eq!(
+        ONE.unit_decimal_places(Some(&[0, 2])).validate(),
+        Err(OptionsError::InvalidFractionDigits)
+    );
+    assert_eq!(
+        BINARY
+            .significant_digits(Some(3))
+            .min_fraction_digits(Some(3))
+            .validate(),
+        Ok(())
+    );
+
+    // Applied with a thousands separator, in scientific notation and with significant digits
+    assert_eq!(
+        format_size(
+            1_048_064_000u64,
+            TRIMMED
+                .thousands_separator(Some(','))
+                .fixed_at(Some(FixedAt::Kilo))
+        ),
+        "1,023,500 KiB"
+    );
+    assert_eq!(
+        format_size(
+            1_500_000u32,
+            DECIMAL
+                .min_fraction_digits(Some(0))
+                .thousands_separator(Some(' '))
+                .fixed_at(Some(FixedAt::Base))
+        ),
+        "1 500 000 B"
+    );
+    assert_eq!(
+        format_size(
+            1500 * 10u128.pow(24),
+            DECIMAL
+                .overflow(Overflow::Scientific)
+                .min_fraction_digits(Some(0))
+        ),
+        "1.5e3 YB"
+    );
+    assert_eq!(
+        format_size(
+            1000 * 10u128.pow(24),
+            DECIMAL
+                .overflow(Overflow::Scientific)
+                .min_fraction_digits(Some(1))
+        ),
+        "1.0e3 YB"
+    );
+    assert_eq!(
+        format_size(
+            1_200_000u32,
+            DECIMAL
+                .significant_digits(Some(4))
+                .min_fraction_digits(Some(0))
+        ),
+        "1.2 MB"
+    );
+    assert_eq!(format_size_i(f64::NAN, ONE), "NaN B");
+
+    assert_eq!(
+        DECIMAL.min_fraction_digits(Some(39)).validate(),
+        Err(OptionsError::TooManyFractionDigits)
+    );
+}
+
+#[test]
+fn max_fraction_digits() {
+    const SIGNIFICANT: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .significant_digits(Some(3))
+        .max_fraction_digits(Some(1));
+    assert_eq!(format_size(12_950_000u32, SIGNIFICANT), "12.4 MiB");
+    assert_eq!(format_size(1_258_291u32, SIGNIFICANT), "1.2 MiB");
+    assert_eq!(format_size_i(1_258_291.0, SIGNIFICANT), "1.2 MiB");
+    assert_eq!(format_size(1024u32, SIGNIFICANT), "1.0 KiB");
+    assert_eq!(
+        format_size(1024u32, BINARY.max_fraction_digits(Some(0))),
+        "1 KiB"
+    );
+    assert_eq!(
+        format_size(1536u32, BINARY.max_fraction_digits(Some(1))),
+        "1.5 KiB"
+    );
+    // The precision flag replaces it
+    assert_eq!(
+        format!("{:.3}", SizeFormatter::new(1536u32, SIGNIFICANT)),
+        "1.500 KiB"
+    );
+
+    // Between the least and the most decimal places
+    const BETWEEN: FormatSizeOptions = FormatSizeOptions::from(BINARY)
+        .decimal_places(3)
+        .min_fraction_digits(Some(1))
+        .max_fraction_digits(Some(2));
+    assert_eq!(format_size(1024u32, BETWEEN), "1.0 KiB");
+    assert_eq!(format_size(1536u32, BETWEEN), "1.5 KiB");
+    assert_eq!(format_size(1300u32, BETWEEN), "1.27 KiB");
+    assert_eq!(BETWEEN.validate(), Ok(()));
+
+    assert_eq!(
+        BETWEEN.min_fraction_digits(Some(3)).validate(),
+        Err(OptionsError::InvalidFractionDigits)
+    );
+    assert_eq!(
+        BINARY
+            .significant_digits(Some(3))
+            .min_fraction_digits(Some(2))
+            .max_fraction_digits(Some(1))
+            .validate(),
+        Err(OptionsError::InvalidFractionDigits)
+    );
+    assert_eq!(
+        BINARY.max_fraction_digits(Some(39)).validate(),
+        Err(OptionsError::TooManyFractionDigits)
+    );
+    assert_eq!(
+        FormatSizeOptionsBuilder::new(BINARY)
+            .min_fraction_digits(Some(3))
+            .max_fraction_digits(Some(1))
+            .build()
+            .err(),
+        Some(OptionsError::InvalidFractionDigits)
+    );
+}
+
+#[test]
+fn scale_for() {
+    fn unit(options: FormatSizeOptions, value: impl ToF64) -> (Option<FixedAt>, f64) {
+        let scale = options.scale_for(value);
+        (scale.fixed_at, scale.value)
+    }
+
+    assert_eq!(
+        BINARY.scale_for(0u32),
+        SizeScale {
+            scale: 0,
+            fixed_at: Some(FixedAt::Base),
+            value: 0.0
+        }
+    );
+    assert_eq!(
+        BINARY.scale_for(1536u32),
+        SizeScale {
+            scale: 1,
+            fixed_at: Some(FixedAt::Kilo),
+            value: 1.5
+        }
+    );
+    assert_eq!(unit(DECIMAL, 2_500_000u32), (Some(FixedAt::Mega), 2.5));
+    assert_eq!(unit(DECIMAL, -2_500_000), (Some(FixedAt::Mega), -2.5));
+    // The displayed value decides the unit, as when formatting
+    assert_eq!(DECIMAL.scale_for(999_999u32).scale, 2);
+    assert_eq!(DECIMAL.decimal_places(3).scale_for(999_999u32).scale, 1);
+    assert_eq!(
+        unit(BINARY.fixed_at(Some(FixedAt::Giga)), 1_073_741_824u64 / 2),
+        (Some(FixedAt::Giga), 0.5)
+    );
+    assert_eq!(
+        unit(BINARY.min_unit(Some(FixedAt::Kilo)), 5u32),
+        (Some(FixedAt::Kilo), 5.0 / 1024.0)
+    );
+    assert_eq!(
+        unit(BINARY.max_unit(Some(FixedAt::Mega)), 1u64 << 40),
+        (Some(FixedAt::Mega), 1_048_576.0)
+    );
+    assert_eq!(
+        unit(DECIMAL.rollover_threshold(Some(900)), 950u32),
+        (Some(FixedAt::Kilo), 0.95)
+    );
+    assert_eq!(DECIMAL.scale_for(f64::NAN).scale, 0);
+
+    const LADDER: &[ScaleStep] = &[ScaleStep::new(1, "B"), ScaleStep::new(1_000_000, "MB")];
+    assert_eq!(
+        unit(DECIMAL.scale_ladder(Some(LADDER)), 1_500_000u32),
+        (Some(FixedAt::Kilo), 1.5)
+    );
+
+    // Steps of a ladder beyond the standard units keep their position
+    const STEPS: [ScaleStep; 13] = {
+        let mut steps = [ScaleStep::new(1, "u"); 13];
+        let mut i = 1;
+        while i < steps.len() {
+            steps[i] = ScaleStep::new(steps[i - 1].multiplier * 2, "u");
+            i += 1;
+        }
+        steps
+    };
+    let scale = DECIMAL.scale_ladder(Some(&STEPS)).scale_for(1u64 << 12);
+    assert_eq!((scale.scale, scale.fixed_at), (12, None));
+    assert_eq!(
+        SizeFormatter::new(1u64 << 12, DECIMAL.scale_ladder(Some(&STEPS)))
+            .parts()
+            .scale,
+        12
+    );
+
+    // The scale matches the one formatters display
+    for value in [1u64, 1023, 1024, 1_048_575, 1 << 40, u64::MAX] {
+        let scale = BINARY.scale_for(value);
+        assert_eq!(SizeFormatter::new(value, BINARY).parts().scale, scale.scale);
+        assert_eq!(
+            format_size(value, BINARY.fixed_at(scale.fixed_at)),
+            format_size(value, BINARY)
+        );
+    }
+}
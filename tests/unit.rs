@@ -0,0 +1,95 @@
+use humansize::{format_size, BaseUnit, FixedAt, Kilo, ParseSizeError, Unit, BINARY, DECIMAL};
+
+#[test]
+fn metadata() {
+    assert_eq!(Unit::Byte.symbol(), "B");
+    assert_eq!(Unit::Byte.long_name(), "Byte");
+    assert_eq!(Unit::Byte.plural(), "Bytes");
+    assert_eq!(Unit::Byte.multiplier(), 1);
+
+    assert_eq!(Unit::Kilobyte.symbol(), "kB");
+    assert_eq!(Unit::Kilobyte.multiplier(), 1000);
+    assert_eq!(Unit::Mebibyte.symbol(), "MiB");
+    assert_eq!(Unit::Mebibyte.long_name(), "Mebibyte");
+    assert_eq!(Unit::Mebibyte.plural(), "Mebibytes");
+    assert_eq!(Unit::Mebibyte.multiplier(), 1 << 20);
+    assert_eq!(Unit::Quettabyte.multiplier(), 10u128.pow(30));
+    assert_eq!(Unit::Quebibyte.multiplier(), 1 << 100);
+
+    assert_eq!(Unit::Bit.symbol(), "bits");
+    assert_eq!(Unit::Bit.long_name(), "Bit");
+    assert_eq!(Unit::Gigabit.symbol(), "Gbit");
+    assert_eq!(Unit::Gigabit.multiplier(), 1_000_000_000);
+    assert_eq!(Unit::Kibibit.symbol(), "Kibit");
+    assert_eq!(Unit::Kibibit.long_name(), "Kibibit");
+    assert_eq!(Unit::Kibibit.multiplier(), 1024);
+
+    assert!(matches!(Unit::Mebibyte.scale(), FixedAt::Mega));
+    assert_eq!(Unit::Mebibyte.kilo(), Kilo::Binary);
+    assert_eq!(Unit::Mebibyte.base_unit(), BaseUnit::Byte);
+    assert_eq!(Unit::Byte.kilo(), Kilo::Decimal);
+    assert_eq!(Unit::Terabit.kilo(), Kilo::Decimal);
+    assert_eq!(Unit::Terabit.base_unit(), BaseUnit::Bit);
+    assert!(matches!(FixedAt::from(Unit::Quebibit), FixedAt::Quetta));
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(
+        Unit::new(FixedAt::Kilo, Kilo::Binary, BaseUnit::Byte),
+        Unit::Kibibyte
+    );
+    assert_eq!(
+        Unit::new(FixedAt::Kilo, Kilo::Jedec, BaseUnit::Byte),
+        Unit::Kibibyte
+    );
+    assert_eq!(
+        Unit::new(FixedAt::Giga, Kilo::Decimal, BaseUnit::Bit),
+        Unit::Gigabit
+    );
+    assert_eq!(
+        Unit::new(FixedAt::Base, Kilo::Binary, BaseUnit::Bit),
+        Unit::Bit
+    );
+    assert_eq!(
+        Unit::new(FixedAt::Mega, Kilo::Custom(512), BaseUnit::Byte),
+        Unit::Megabyte
+    );
+    assert_eq!(
+        Unit::new(FixedAt::Quetta, Kilo::Binary, BaseUnit::Bit),
+        Unit::Quebibit
+    );
+
+    assert_eq!("MiB".parse::<Unit>(), Ok(Unit::Mebibyte));
+    assert_eq!("mB".parse::<Unit>(), Ok(Unit::Megabyte));
+    assert_eq!("mb".parse::<Unit>(), Ok(Unit::Megabit));
+    assert_eq!("KB".parse::<Unit>(), Ok(Unit::Kibibyte));
+    assert_eq!("Gigabits".parse::<Unit>(), Ok(Unit::Gigabit));
+    assert_eq!(" kibibyte ".parse::<Unit>(), Ok(Unit::Kibibyte));
+    assert_eq!("bit".parse::<Unit>(), Ok(Unit::Bit));
+    assert_eq!("B".parse::<Unit>(), Ok(Unit::Byte));
+    assert_eq!("parsecs".parse::<Unit>(), Err(ParseSizeError::UnknownUnit));
+    assert_eq!("".parse::<Unit>(), Err(ParseSizeError::Empty));
+
+    assert_eq!(Unit::Tebibyte.to_string(), "TiB");
+    assert_eq!(format!("{:#}", Unit::Tebibyte), "Tebibytes");
+    assert_eq!(format!("{:>5}", Unit::Kilobyte), "   kB");
+}
+
+#[test]
+fn matches_formatters() {
+    for (scale, value) in [
+        (FixedAt::Kilo, 1u64 << 10),
+        (FixedAt::Giga, 1 << 30),
+        (FixedAt::Exa, 1 << 60),
+    ] {
+        let unit = Unit::new(scale, Kilo::Binary, BaseUnit::Byte);
+        assert_eq!(unit.multiplier(), value as u128);
+        assert_eq!(format_size(value, BINARY), format!("1 {}", unit));
+    }
+    let unit = Unit::new(FixedAt::Tera, Kilo::Decimal, BaseUnit::Byte);
+    assert_eq!(
+        format_size(unit.multiplier() as u64 * 3, DECIMAL.long_units(true)),
+        format!("3 {:#}", unit)
+    );
+}